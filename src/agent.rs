@@ -0,0 +1,124 @@
+// agent.rs
+// a vehicle steered by a list of behaviors, what the demos are built from
+use ggez::glam::{Mat4, Vec2};
+use ggez::graphics::{self, Color, DrawParam};
+use ggez::{Context, GameResult};
+
+use crate::behaviors::{BoxedBehavior, SteeringBehavior, World};
+use crate::local_space::LocalSpace;
+use crate::simple_vehicle::{SimpleVehicle, REFERENCE_TIME_STEP};
+use crate::vector::Vector;
+use crate::vector_space::VectorSpace;
+
+pub struct Agent<V: VectorSpace = Vector> {
+    pub vehicle: SimpleVehicle<V>,
+    // summed every step, the vehicle truncates the total to max_force
+    pub behaviors: Vec<BoxedBehavior<V>>,
+    // the total from the last update, kept for drawing
+    pub steering: V,
+}
+
+impl<V: VectorSpace> Agent<V> {
+    pub fn new(vehicle: SimpleVehicle<V>) -> Self {
+        Agent {
+            vehicle,
+            behaviors: Vec::new(),
+            steering: V::ZERO,
+        }
+    }
+
+    pub fn with_behavior(mut self, behavior: impl SteeringBehavior<V> + Send + 'static) -> Self {
+        self.behaviors.push(Box::new(behavior));
+        self
+    }
+
+    // sums the behaviors' forces, applies them and advances the vehicle by dt seconds
+    pub fn update(&mut self, world: &World<V>, dt: f32) {
+        let vehicle = &self.vehicle;
+        let steering = self
+            .behaviors
+            .iter_mut()
+            .fold(V::ZERO, |total, behavior| total + behavior.steering_force(vehicle, world));
+        self.steer(steering, dt);
+    }
+
+    // applies steering worked out by the caller, for behaviors a demo keeps outside the list
+    // to draw their state, and advances the vehicle by dt seconds
+    pub fn steer(&mut self, steering: V, dt: f32) {
+        self.steering = steering;
+        self.vehicle.apply_global_force(steering);
+        self.vehicle.update(dt);
+    }
+}
+
+// drawing needs the vector on screen and the vehicle's local space as a screen transform
+impl<V: VectorSpace + Into<Vec2>> Agent<V>
+where
+    for<'a> Mat4: From<&'a LocalSpace<V>>,
+{
+    // trail, smoothed position, the vehicle itself and its steering and velocity
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut graphics::Canvas, color: Color) -> GameResult {
+        // faded a little so the trail sits behind the vehicle
+        let trail_color = Color { a: 0.6, ..color };
+        self.vehicle.trail.draw(ctx, canvas, self.vehicle.local_space.position, trail_color)?;
+
+        // running average of recent positions
+        let smoothed_position = graphics::Mesh::new_circle(
+            ctx,
+            graphics::DrawMode::fill(),
+            self.vehicle.smoothed_position.into(),
+            2.5,
+            0.2,
+            Color::from_rgb(96, 96, 96),
+        )?;
+        canvas.draw(&smoothed_position, DrawParam::default());
+
+        // the vehicle is a triangle in its own local space, nose along forward (local y),
+        // one transform from the local space places and orients it
+        let vehicle_radius = self.vehicle.params.radius;
+        let hull = [
+            Vec2::new(0.0, vehicle_radius * 1.5),
+            Vec2::new(-vehicle_radius, -vehicle_radius),
+            Vec2::new(vehicle_radius, -vehicle_radius),
+        ];
+        let vehicle = graphics::Mesh::new_polygon(ctx, graphics::DrawMode::fill(), &hull, color)?;
+        let vehicle_border = graphics::Mesh::new_polygon(ctx, graphics::DrawMode::stroke(2.0), &hull, Color::BLACK)?;
+        let vehicle_transform = DrawParam::default().transform(Mat4::from(&self.vehicle.local_space));
+        canvas.draw(&vehicle, vehicle_transform);
+        canvas.draw(&vehicle_border, vehicle_transform);
+
+        // scales were picked for per-frame units, steering is per second squared and velocity per second
+        self.draw_vector(self.steering, 300.0 * REFERENCE_TIME_STEP * REFERENCE_TIME_STEP, Color::BLUE, ctx, canvas);
+        self.draw_vector(self.vehicle.velocity, 40.0 * REFERENCE_TIME_STEP, Color::MAGENTA, ctx, canvas);
+        Ok(())
+    }
+
+    // draws vec scaled by scale as a line starting at the vehicle
+    pub fn draw_vector(&self, vec: V, scale: f32, color: Color, ctx: &mut Context, canvas: &mut graphics::Canvas) {
+        let end = self.vehicle.local_space.position + vec * scale;
+        let (start, finish): (Vec2, Vec2) = (self.vehicle.local_space.position.into(), end.into());
+
+        // a zero length line fails to build, there is nothing to draw then
+        if let Ok(line) = graphics::Mesh::new_line(ctx, &[start, finish], 2.5, color) {
+            canvas.draw(&line, DrawParam::default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behaviors::{Flee, Seek};
+    use crate::math_mode::MathMode;
+    use crate::vector2d::Vector2D;
+
+    #[test]
+    fn behaviors_are_summed() {
+        let vehicle = SimpleVehicle::new(Vector2D::new(10.0, 0.0), MathMode::Exact);
+        let mut agent = Agent::new(vehicle).with_behavior(Seek::new()).with_behavior(Flee::new());
+        let world = World::new(Vector2D::ZERO, REFERENCE_TIME_STEP);
+        agent.update(&world, REFERENCE_TIME_STEP);
+        assert!(agent.steering.approx_eq(Vector2D::ZERO));
+        assert_eq!(agent.vehicle.local_space.position, Vector2D::new(10.0, 0.0));
+    }
+}
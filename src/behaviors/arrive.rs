@@ -0,0 +1,115 @@
+// behaviors/arrive.rs
+// seek that slows down inside a radius around world.target and comes to rest on it
+use crate::behaviors::{steer_towards_velocity, SteeringBehavior, World};
+use crate::simple_vehicle::SimpleVehicle;
+use crate::vector_space::VectorSpace;
+
+// how the desired speed falls off inside the slowing radius, as a share of max_speed
+// for the share of the radius still to go
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ArriveProfile {
+    // speed proportional to distance, as in OpenSteer, settles exponentially
+    #[default]
+    Linear,
+    // constant deceleration, the distance left falls off quadratically in time and the
+    // vehicle stops in finite time, speed goes with the square root of distance
+    Quadratic,
+    // t * (2 - t), keeps speed up longer than linear and eases into the stop
+    EaseOut,
+}
+
+impl ArriveProfile {
+    pub fn speed_share(self, distance_share: f32) -> f32 {
+        let t = distance_share.clamp(0.0, 1.0);
+        match self {
+            ArriveProfile::Linear => t,
+            ArriveProfile::Quadratic => t.sqrt(),
+            ArriveProfile::EaseOut => t * (2.0 - t),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ArriveProfile::Linear => "linear",
+            ArriveProfile::Quadratic => "quadratic",
+            ArriveProfile::EaseOut => "ease-out",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Arrive {
+    // world units, outside it arrive is plain seek at max_speed
+    pub slowing_radius: f32,
+    pub profile: ArriveProfile,
+}
+
+impl Arrive {
+    pub fn new(slowing_radius: f32, profile: ArriveProfile) -> Self {
+        Arrive { slowing_radius, profile }
+    }
+}
+
+impl<V: VectorSpace> SteeringBehavior<V> for Arrive {
+    fn name(&self) -> &'static str {
+        "arrive"
+    }
+
+    fn steering_force(&mut self, vehicle: &SimpleVehicle<V>, world: &World<V>) -> V {
+        let offset = world.target - vehicle.local_space.position;
+        let distance = vehicle.math.length(offset);
+        if distance <= 0.0 {
+            return steer_towards_velocity(vehicle, V::ZERO);
+        }
+
+        let share = if self.slowing_radius > 0.0 { distance / self.slowing_radius } else { 1.0 };
+        let speed = vehicle.params.max_speed * self.profile.speed_share(share);
+        // the acceleration already built up keeps changing the velocity for a while behind
+        // acceleration_damping, steering for the desired velocity alone makes up for it again
+        // every step and the vehicle keeps circling the target
+        let desired = offset * (speed / distance);
+        steer_towards_velocity(vehicle, desired - vehicle.pending_velocity())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_mode::MathMode;
+    use crate::simple_vehicle::REFERENCE_TIME_STEP;
+    use crate::vector2d::Vector2D;
+    use crate::vehicle_params::VehicleParams;
+
+    #[test]
+    fn profiles_run_from_rest_to_full_speed() {
+        for profile in [ArriveProfile::Linear, ArriveProfile::Quadratic, ArriveProfile::EaseOut] {
+            assert_eq!(profile.speed_share(0.0), 0.0);
+            assert_eq!(profile.speed_share(1.0), 1.0);
+            assert_eq!(profile.speed_share(3.0), 1.0);
+        }
+        assert!(ArriveProfile::Quadratic.speed_share(0.5) > ArriveProfile::Linear.speed_share(0.5));
+        assert!(ArriveProfile::EaseOut.speed_share(0.5) > ArriveProfile::Linear.speed_share(0.5));
+    }
+
+    #[test]
+    fn comes_to_rest_on_the_target() {
+        for (preset, params) in [("demo", VehicleParams::demo()), ("opensteer", VehicleParams::opensteer())] {
+            for profile in [ArriveProfile::Linear, ArriveProfile::Quadratic, ArriveProfile::EaseOut] {
+                // starting across the target so the vehicle has to turn round, from a few
+                // seconds away at max speed
+                let mut vehicle = SimpleVehicle::with_params(Vector2D::new(-2.0, 1.0) * params.max_speed, MathMode::Exact, params);
+                vehicle.velocity = Vector2D::new(0.0, -params.max_speed);
+                let mut arrive = Arrive::new(2.0 * params.max_speed, profile);
+                let world = World::new(Vector2D::ZERO, REFERENCE_TIME_STEP);
+                for _ in 0..1800 {
+                    let force = arrive.steering_force(&vehicle, &world);
+                    vehicle.apply_global_force(force);
+                    vehicle.update(REFERENCE_TIME_STEP);
+                }
+                let (position, velocity) = (vehicle.local_space.position, vehicle.velocity);
+                assert!(position.length() < 0.1, "{} {} ended at {:?}", preset, profile.name(), position);
+                assert!(velocity.length() < 0.1, "{} {} still moving at {:?}", preset, profile.name(), velocity);
+            }
+        }
+    }
+}
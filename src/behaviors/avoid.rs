@@ -0,0 +1,165 @@
+// behaviors/avoid.rs
+// obstacle avoidance, sweeps the path ahead and steers sideways away from the nearest obstacle on it
+use crate::behaviors::{steer_towards_velocity, SteeringBehavior, World};
+use crate::obstacle::{PathIntersection, SphereObstacle};
+use crate::simple_vehicle::SimpleVehicle;
+use crate::vector_space::VectorSpace;
+
+// share of the vehicle's radius added on for the sweep. a damped vehicle passing close by an
+// obstacle drifts in while it answers the turn, this starts the turn before it brushes past
+const SWEEP_MARGIN: f32 = 0.5;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ObstacleAvoidance {
+    // seconds of travel at the current speed to look ahead along forward
+    pub lookahead_time: f32,
+}
+
+impl ObstacleAvoidance {
+    pub fn new(lookahead_time: f32) -> Self {
+        ObstacleAvoidance { lookahead_time }
+    }
+
+    // the first obstacle the vehicle would run into within the lookahead, by distance along the
+    // path, with SWEEP_MARGIN to spare
+    pub fn nearest_threat<'w, V: VectorSpace>(
+        &self,
+        vehicle: &SimpleVehicle<V>,
+        world: &World<'w, V>,
+    ) -> Option<(&'w SphereObstacle<V>, PathIntersection<V>)> {
+        let length = self.lookahead_distance(vehicle);
+        world
+            .obstacles
+            .iter()
+            .filter_map(|obstacle| {
+                let hit = obstacle.path_intersection(&vehicle.local_space, vehicle.params.radius * (1.0 + SWEEP_MARGIN), length)?;
+                Some((obstacle, hit))
+            })
+            .min_by(|(_, a), (_, b)| a.distance.total_cmp(&b.distance))
+    }
+
+    // how far the sweep reaches, never shorter than the vehicle itself so a slow vehicle still looks.
+    // the vehicle's response time is added on, a damped vehicle covers that much ground before
+    // the turn really starts
+    pub fn lookahead_distance<V: VectorSpace>(&self, vehicle: &SimpleVehicle<V>) -> f32 {
+        let lookahead_time = self.lookahead_time + vehicle.response_time();
+        (vehicle.math.length(vehicle.velocity) * lookahead_time).max(vehicle.params.radius * 2.0)
+    }
+}
+
+impl<V: VectorSpace> SteeringBehavior<V> for ObstacleAvoidance {
+    fn name(&self) -> &'static str {
+        "obstacle-avoidance"
+    }
+
+    // steers for max_speed straight across forward, away from the side the threat's center is
+    // on, which brakes as well as turns. a flat push across is mostly spent turning the damped
+    // acceleration other behaviors built up, and the vehicle slides into the next obstacle.
+    // a threat dead ahead is passed on the side of side
+    fn steering_force(&mut self, vehicle: &SimpleVehicle<V>, world: &World<V>) -> V {
+        let Some((_, threat)) = self.nearest_threat(vehicle, world) else {
+            return V::ZERO;
+        };
+        let away = -threat.lateral_offset.normalized();
+        let away = if away == V::ZERO { V::SIDE } else { away };
+        steer_towards_velocity(vehicle, vehicle.local_space.globalize_direction(away) * vehicle.params.max_speed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behaviors::{Prioritized, Seek};
+    use crate::math_mode::MathMode;
+    use crate::obstacle::SphereObstacle;
+    use crate::simple_vehicle::REFERENCE_TIME_STEP;
+    use crate::vector2d::Vector2D;
+    use crate::vehicle_params::VehicleParams;
+
+    #[test]
+    fn steers_away_from_the_nearest_threat() {
+        // heading +y, side is +x. undamped, so the lookahead is just speed times lookahead_time
+        let params = VehicleParams::builder().acceleration_damping(0.0).build();
+        let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+        vehicle.velocity = Vector2D::new(0.0, 30.0);
+        let obstacles = [
+            SphereObstacle::new(Vector2D::new(-4.0, 70.0), 10.0),
+            SphereObstacle::new(Vector2D::new(5.0, 40.0), 10.0),
+            SphereObstacle::new(Vector2D::new(-60.0, 20.0), 10.0),
+        ];
+        let world = World::new(Vector2D::ZERO, REFERENCE_TIME_STEP).with_obstacles(&obstacles);
+        let mut avoid = ObstacleAvoidance::new(3.0);
+
+        let (obstacle, threat) = avoid.nearest_threat(&vehicle, &world).expect("two obstacles are in the way");
+        assert_eq!(obstacle, &obstacles[1]);
+        assert!(threat.lateral_offset.approx_eq(Vector2D::new(5.0, 0.0)));
+        // across to -x, and braking
+        let steering = avoid.steering_force(&vehicle, &world);
+        let across = Vector2D::new(-vehicle.params.max_speed, 0.0);
+        assert!(steering.approx_eq(steer_towards_velocity(&vehicle, across)));
+        assert!(steering.x < 0.0 && steering.y < 0.0);
+
+        // half a second only reaches 15 ahead, short of where the swept hull meets the nearest
+        assert_eq!(ObstacleAvoidance::new(0.5).steering_force(&vehicle, &world), Vector2D::ZERO);
+
+        // with the preset's damping it looks a response time further
+        vehicle.params = VehicleParams::demo();
+        let lookahead = ObstacleAvoidance::new(0.5).lookahead_distance(&vehicle);
+        assert!((lookahead - 30.0 * (0.5 + vehicle.response_time())).abs() < 1.0e-3, "looks {} ahead", lookahead);
+        assert!(vehicle.response_time() > 1.0);
+    }
+
+    #[test]
+    fn seeker_goes_around_an_obstacle_in_the_way() {
+        // the target is just behind the obstacle. summed with avoidance, seek pulls back towards
+        // it hard enough that the vehicle never gets there
+        for (preset, params) in [("demo", VehicleParams::demo()), ("opensteer", VehicleParams::opensteer())] {
+            let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+            vehicle.velocity = Vector2D::new(0.0, params.max_speed);
+            let obstacles = [SphereObstacle::new(Vector2D::new(0.0, 100.0), 30.0)];
+            let target = Vector2D::new(0.0, 140.0);
+            let world = World::new(target, REFERENCE_TIME_STEP).with_obstacles(&obstacles);
+            let mut steering = Prioritized::new().with_behavior(ObstacleAvoidance::new(1.5)).with_behavior(Seek::new());
+
+            // the opensteer preset is an eighth of the speed and takes over a minute to get round
+            let mut reached = false;
+            for _ in 0..14400 {
+                let force = steering.steering_force(&vehicle, &world);
+                vehicle.apply_global_force(force);
+                vehicle.update(REFERENCE_TIME_STEP);
+
+                // the hull may graze the edge, but never sinks in past half its radius
+                let clearance = vehicle.local_space.position.distance(obstacles[0].center) - obstacles[0].radius;
+                assert!(clearance > 0.5 * params.radius, "{} entered the obstacle at {:?}", preset, vehicle.local_space.position);
+                reached |= vehicle.local_space.position.distance(target) <= params.radius;
+            }
+            assert!(reached, "{} never got to the target", preset);
+        }
+    }
+
+    #[test]
+    fn seeker_keeps_out_of_a_narrow_gap() {
+        // two obstacles side by side with a gap the vehicle does not fit through, the target
+        // is beyond them. dodging one mustn't carry the vehicle into the other
+        for (preset, params) in [("demo", VehicleParams::demo()), ("opensteer", VehicleParams::opensteer())] {
+            let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+            vehicle.velocity = Vector2D::new(0.0, params.max_speed);
+            let obstacles = [
+                SphereObstacle::new(Vector2D::new(-35.0, 100.0), 35.0),
+                SphereObstacle::new(Vector2D::new(45.0, 100.0), 35.0),
+            ];
+            let world = World::new(Vector2D::new(40.0, 220.0), REFERENCE_TIME_STEP).with_obstacles(&obstacles);
+            let mut steering = Prioritized::new().with_behavior(ObstacleAvoidance::new(1.5)).with_behavior(Seek::new());
+
+            for _ in 0..3600 {
+                let force = steering.steering_force(&vehicle, &world);
+                vehicle.apply_global_force(force);
+                vehicle.update(REFERENCE_TIME_STEP);
+                for obstacle in &obstacles {
+                    let clearance = vehicle.local_space.position.distance(obstacle.center) - obstacle.radius;
+                    assert!(clearance > 0.5 * params.radius, "{} entered an obstacle at {:?}", preset, vehicle.local_space.position);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// behaviors/mod.rs
+// steering behaviors, each one turns the vehicle and what it can see of the world into a force
+use crate::obstacle::SphereObstacle;
+use crate::simple_vehicle::{SimpleVehicle, REFERENCE_TIME_STEP};
+use crate::vector_space::VectorSpace;
+
+pub mod seek;
+pub mod arrive;
+pub mod pursue;
+pub mod wander;
+pub mod avoid;
+pub mod priority;
+
+pub use seek::{Flee, Seek};
+pub use arrive::{Arrive, ArriveProfile};
+pub use pursue::{Evade, Pursue};
+pub use wander::Wander;
+pub use avoid::ObstacleAvoidance;
+pub use priority::Prioritized;
+
+// what a behavior may look at besides its own vehicle, built fresh by the demo every step
+pub struct World<'a, V: VectorSpace> {
+    // the point the demo is steering about
+    pub target: V,
+    // another vehicle to react to, e.g. a quarry to pursue
+    pub target_vehicle: Option<&'a SimpleVehicle<V>>,
+    // things to steer around, empty unless the demo has some
+    pub obstacles: &'a [SphereObstacle<V>],
+    // seconds covered by this step
+    pub time_step: f32,
+}
+
+impl<'a, V: VectorSpace> World<'a, V> {
+    pub fn new(target: V, time_step: f32) -> Self {
+        World {
+            target,
+            target_vehicle: None,
+            obstacles: &[],
+            time_step,
+        }
+    }
+
+    pub fn with_target_vehicle(mut self, vehicle: &'a SimpleVehicle<V>) -> Self {
+        self.target_vehicle = Some(vehicle);
+        self
+    }
+
+    pub fn with_obstacles(mut self, obstacles: &'a [SphereObstacle<V>]) -> Self {
+        self.obstacles = obstacles;
+        self
+    }
+}
+
+pub trait SteeringBehavior<V: VectorSpace> {
+    fn name(&self) -> &'static str;
+
+    // force to apply this step, in world units per second squared. &mut self lets
+    // behaviors such as wander keep state between steps
+    fn steering_force(&mut self, vehicle: &SimpleVehicle<V>, world: &World<V>) -> V;
+}
+
+// behaviors are boxed per agent, Send like BoxedIntegrator
+pub type BoxedBehavior<V> = Box<dyn SteeringBehavior<V> + Send>;
+
+// the acceleration that would turn velocity into desired within one reference step, at most max_force
+pub fn steer_towards_velocity<V: VectorSpace>(vehicle: &SimpleVehicle<V>, desired: V) -> V {
+    vehicle.math.truncate((desired - vehicle.velocity) / REFERENCE_TIME_STEP, vehicle.params.max_force)
+}
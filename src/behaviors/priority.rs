@@ -0,0 +1,44 @@
+// behaviors/priority.rs
+// a list of behaviors where the first one with something to say wins, as OpenSteer combines
+// obstacle avoidance with seeking so a target behind an obstacle can't cancel the avoidance
+use crate::behaviors::{BoxedBehavior, SteeringBehavior, World};
+use crate::simple_vehicle::SimpleVehicle;
+use crate::vector_space::VectorSpace;
+
+pub struct Prioritized<V: VectorSpace> {
+    // most important first
+    pub behaviors: Vec<BoxedBehavior<V>>,
+}
+
+impl<V: VectorSpace> Prioritized<V> {
+    pub fn new() -> Self {
+        Prioritized { behaviors: Vec::new() }
+    }
+
+    // adds a behavior below the ones already in the list
+    pub fn with_behavior(mut self, behavior: impl SteeringBehavior<V> + Send + 'static) -> Self {
+        self.behaviors.push(Box::new(behavior));
+        self
+    }
+}
+
+impl<V: VectorSpace> Default for Prioritized<V> {
+    fn default() -> Self {
+        Prioritized::new()
+    }
+}
+
+impl<V: VectorSpace> SteeringBehavior<V> for Prioritized<V> {
+    fn name(&self) -> &'static str {
+        "prioritized"
+    }
+
+    // lower behaviors are not asked at all once one answers, so they keep no state from this step
+    fn steering_force(&mut self, vehicle: &SimpleVehicle<V>, world: &World<V>) -> V {
+        self.behaviors
+            .iter_mut()
+            .map(|behavior| behavior.steering_force(vehicle, world))
+            .find(|force| *force != V::ZERO)
+            .unwrap_or(V::ZERO)
+    }
+}
@@ -0,0 +1,199 @@
+// behaviors/pursue.rs
+// pursue seeks and evade flees world.target_vehicle's predicted position rather than where it is now
+use crate::behaviors::{Flee, Seek, SteeringBehavior, World};
+use crate::simple_vehicle::SimpleVehicle;
+use crate::vector_space::VectorSpace;
+
+// cos 45 degrees, splits headings into ahead/aside/behind and parallel/perpendicular/anti-parallel
+const HEADING_SPLIT: f32 = 0.707;
+// share of max_speed the travel time is worked out with when the pursuer is slower than that
+const MIN_SPEED_SHARE: f32 = 0.1;
+
+// -1, 0 or 1 for below, inside or above the interval
+fn interval_comparison(value: f32, lower: f32, upper: f32) -> i32 {
+    if value < lower {
+        -1
+    } else if value > upper {
+        1
+    } else {
+        0
+    }
+}
+
+// seconds to look ahead, with the travel time scaled by where the quarry is and which way it
+// is heading relative to us, the table from Reynolds' paper as OpenSteer has it
+pub fn pursuit_prediction_time<V: VectorSpace>(
+    vehicle: &SimpleVehicle<V>,
+    quarry: &SimpleVehicle<V>,
+    max_prediction_time: Option<f32>,
+) -> f32 {
+    let offset = quarry.local_space.position - vehicle.local_space.position;
+    let distance = vehicle.math.length(offset);
+    if distance <= 0.0 {
+        return 0.0;
+    }
+
+    let forwardness = vehicle.local_space.forward.dot(offset / distance);
+    let parallelness = vehicle.local_space.forward.dot(quarry.local_space.forward);
+    let time_factor = match (
+        interval_comparison(forwardness, -HEADING_SPLIT, HEADING_SPLIT),
+        interval_comparison(parallelness, -HEADING_SPLIT, HEADING_SPLIT),
+    ) {
+        // quarry ahead
+        (1, 1) => 4.0,
+        (1, 0) => 1.8,
+        (1, _) => 0.85,
+        // quarry aside
+        (0, 1) => 1.0,
+        (0, 0) => 0.8,
+        (0, _) => 4.0,
+        // quarry behind
+        (_, 1) => 0.5,
+        (_, 0) => 2.0,
+        (_, _) => 2.0,
+    };
+
+    // a stopped or crawling pursuer counts as moving at 10% of max_speed. at its real speed
+    // the travel time, and with it how far ahead the quarry is predicted, grows without bound
+    let speed = vehicle.math.length(vehicle.velocity).max(vehicle.params.max_speed * MIN_SPEED_SHARE);
+    let time = distance / speed * time_factor;
+    max_prediction_time.map_or(time, |max| time.min(max))
+}
+
+// seconds until the menace could reach us at its current speed
+pub fn evasion_prediction_time<V: VectorSpace>(
+    vehicle: &SimpleVehicle<V>,
+    menace: &SimpleVehicle<V>,
+    max_prediction_time: Option<f32>,
+) -> f32 {
+    let distance = vehicle.math.distance(menace.local_space.position, vehicle.local_space.position);
+    let menace_speed = menace.math.length(menace.velocity);
+    let time = if menace_speed > 0.0 { distance / menace_speed } else { 0.0 };
+    max_prediction_time.map_or(time, |max| time.min(max))
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Pursue {
+    // seconds, None lets the prediction grow with distance
+    pub max_prediction_time: Option<f32>,
+}
+
+impl Pursue {
+    pub fn new(max_prediction_time: Option<f32>) -> Self {
+        Pursue { max_prediction_time }
+    }
+
+    // where the quarry is expected to be, what pursue seeks
+    pub fn predicted_position<V: VectorSpace>(&self, vehicle: &SimpleVehicle<V>, quarry: &SimpleVehicle<V>) -> V {
+        quarry.predict_future_position(pursuit_prediction_time(vehicle, quarry, self.max_prediction_time))
+    }
+}
+
+impl<V: VectorSpace> SteeringBehavior<V> for Pursue {
+    fn name(&self) -> &'static str {
+        "pursue"
+    }
+
+    // nothing to pursue without world.target_vehicle
+    fn steering_force(&mut self, vehicle: &SimpleVehicle<V>, world: &World<V>) -> V {
+        match world.target_vehicle {
+            Some(quarry) => {
+                let predicted = World::new(self.predicted_position(vehicle, quarry), world.time_step);
+                Seek::new().steering_force(vehicle, &predicted)
+            }
+            None => V::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Evade {
+    // seconds, None lets the prediction grow with distance
+    pub max_prediction_time: Option<f32>,
+}
+
+impl Evade {
+    pub fn new(max_prediction_time: Option<f32>) -> Self {
+        Evade { max_prediction_time }
+    }
+
+    // where the menace is expected to be, what evade flees
+    pub fn predicted_position<V: VectorSpace>(&self, vehicle: &SimpleVehicle<V>, menace: &SimpleVehicle<V>) -> V {
+        menace.predict_future_position(evasion_prediction_time(vehicle, menace, self.max_prediction_time))
+    }
+}
+
+impl<V: VectorSpace> SteeringBehavior<V> for Evade {
+    fn name(&self) -> &'static str {
+        "evade"
+    }
+
+    // nothing to evade without world.target_vehicle
+    fn steering_force(&mut self, vehicle: &SimpleVehicle<V>, world: &World<V>) -> V {
+        match world.target_vehicle {
+            Some(menace) => {
+                let predicted = World::new(self.predicted_position(vehicle, menace), world.time_step);
+                Flee::new().steering_force(vehicle, &predicted)
+            }
+            None => V::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_mode::MathMode;
+    use crate::simple_vehicle::REFERENCE_TIME_STEP;
+    use crate::vector2d::Vector2D;
+
+    // pursuer at the origin heading +y at max speed, quarry ahead crossing to +x
+    fn chase() -> (SimpleVehicle<Vector2D>, SimpleVehicle<Vector2D>) {
+        let mut pursuer = SimpleVehicle::new(Vector2D::ZERO, MathMode::Exact);
+        pursuer.local_space.set_heading_angle(std::f32::consts::FRAC_PI_2);
+        pursuer.velocity = pursuer.local_space.forward * pursuer.params.max_speed;
+        let mut quarry = SimpleVehicle::new(Vector2D::new(0.0, 100.0), MathMode::Exact);
+        quarry.local_space.set_heading_angle(0.0);
+        quarry.velocity = Vector2D::new(20.0, 0.0);
+        (pursuer, quarry)
+    }
+
+    #[test]
+    fn prediction_time_follows_the_heading_table() {
+        let (pursuer, quarry) = chase();
+        // ahead and perpendicular
+        let travel_time = 100.0 / pursuer.params.max_speed;
+        assert!((pursuit_prediction_time(&pursuer, &quarry, None) - travel_time * 1.8).abs() < 1.0e-4);
+        assert_eq!(pursuit_prediction_time(&pursuer, &quarry, Some(0.5)), 0.5);
+        assert!((evasion_prediction_time(&quarry, &pursuer, None) - travel_time).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn stopped_pursuer_predicts_at_a_tenth_of_max_speed() {
+        let (mut pursuer, quarry) = chase();
+        pursuer.velocity = Vector2D::ZERO;
+        let travel_time = 100.0 / (pursuer.params.max_speed * 0.1);
+        let time = pursuit_prediction_time(&pursuer, &quarry, None);
+        assert!((time - travel_time * 1.8).abs() < 1.0e-3, "predicted {} s ahead", time);
+        assert_eq!(pursuit_prediction_time(&pursuer, &quarry, Some(2.0)), 2.0);
+    }
+
+    #[test]
+    fn pursue_leads_and_evade_runs_from_the_prediction() {
+        let (pursuer, quarry) = chase();
+        let world = World::new(quarry.local_space.position, REFERENCE_TIME_STEP).with_target_vehicle(&quarry);
+        // the quarry moves towards +x, pursuit steers that way to cut it off
+        let steering = Pursue::new(None).steering_force(&pursuer, &world);
+        assert!(steering.x > 0.0);
+
+        let world = World::new(pursuer.local_space.position, REFERENCE_TIME_STEP).with_target_vehicle(&pursuer);
+        let mut evade = Evade::new(Some(1.0));
+        let predicted = evade.predicted_position(&quarry, &pursuer);
+        assert!(predicted.approx_eq(Vector2D::new(0.0, pursuer.params.max_speed)));
+        let steering = evade.steering_force(&quarry, &world);
+        assert!(steering.dot(quarry.local_space.position - predicted) > 0.0);
+
+        let alone = World::new(Vector2D::ZERO, REFERENCE_TIME_STEP);
+        assert_eq!(Pursue::new(None).steering_force(&pursuer, &alone), Vector2D::ZERO);
+    }
+}
@@ -0,0 +1,100 @@
+// behaviors/seek.rs
+// seek heads for world.target, flee heads directly away from it
+use crate::behaviors::{steer_towards_velocity, SteeringBehavior, World};
+use crate::simple_vehicle::SimpleVehicle;
+use crate::vector_space::VectorSpace;
+
+// the original applet capped the desired speed at 1.1 times the current speed
+const APPLET_SPEED_RATIO: f32 = 1.1;
+
+// desired velocity along offset, at max_speed or at ratio times the current speed
+fn desired_velocity<V: VectorSpace>(vehicle: &SimpleVehicle<V>, offset: V, speed_ratio: Option<f32>) -> V {
+    let math = &vehicle.math;
+    match speed_ratio {
+        Some(ratio) => math.truncate(offset, ratio * math.length(vehicle.velocity)),
+        None => offset.normalized() * vehicle.params.max_speed,
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Seek {
+    // None wants max_speed, Some(ratio) wants ratio times the current speed so the vehicle
+    // turns towards the target rather than speeding up, a stopped vehicle then stays put
+    pub speed_ratio: Option<f32>,
+}
+
+impl Seek {
+    pub fn new() -> Self {
+        Seek { speed_ratio: None }
+    }
+
+    // steers like the seek/flee applet, see APPLET_SPEED_RATIO
+    pub fn applet() -> Self {
+        Seek { speed_ratio: Some(APPLET_SPEED_RATIO) }
+    }
+}
+
+impl<V: VectorSpace> SteeringBehavior<V> for Seek {
+    fn name(&self) -> &'static str {
+        "seek"
+    }
+
+    fn steering_force(&mut self, vehicle: &SimpleVehicle<V>, world: &World<V>) -> V {
+        let offset = world.target - vehicle.local_space.position;
+        steer_towards_velocity(vehicle, desired_velocity(vehicle, offset, self.speed_ratio))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Flee {
+    // same as Seek::speed_ratio
+    pub speed_ratio: Option<f32>,
+}
+
+impl Flee {
+    pub fn new() -> Self {
+        Flee { speed_ratio: None }
+    }
+
+    pub fn applet() -> Self {
+        Flee { speed_ratio: Some(APPLET_SPEED_RATIO) }
+    }
+}
+
+impl<V: VectorSpace> SteeringBehavior<V> for Flee {
+    fn name(&self) -> &'static str {
+        "flee"
+    }
+
+    fn steering_force(&mut self, vehicle: &SimpleVehicle<V>, world: &World<V>) -> V {
+        let offset = vehicle.local_space.position - world.target;
+        steer_towards_velocity(vehicle, desired_velocity(vehicle, offset, self.speed_ratio))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_mode::MathMode;
+    use crate::simple_vehicle::REFERENCE_TIME_STEP;
+    use crate::vector2d::Vector2D;
+
+    #[test]
+    fn seek_and_flee_push_opposite_ways() {
+        let vehicle = SimpleVehicle::new(Vector2D::new(10.0, 0.0), MathMode::Exact);
+        let world = World::new(Vector2D::ZERO, REFERENCE_TIME_STEP);
+
+        let seek = Seek::new().steering_force(&vehicle, &world);
+        let flee = Flee::new().steering_force(&vehicle, &world);
+        assert!(seek.x < 0.0 && seek.y.abs() < 1.0e-6);
+        assert!(flee.approx_eq(-seek));
+        assert!((seek.length() - vehicle.params.max_force).abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn applet_seek_keeps_a_stopped_vehicle_still() {
+        let vehicle = SimpleVehicle::new(Vector2D::new(10.0, 0.0), MathMode::Exact);
+        let world = World::new(Vector2D::ZERO, REFERENCE_TIME_STEP);
+        assert_eq!(Seek::applet().steering_force(&vehicle, &world), Vector2D::ZERO);
+    }
+}
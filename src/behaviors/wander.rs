@@ -0,0 +1,99 @@
+// behaviors/wander.rs
+// steers for a point that drifts around a circle (a sphere in 3D) held ahead of the vehicle
+use crate::behaviors::{steer_towards_velocity, SteeringBehavior, World};
+use crate::random::{self, SimRng};
+use crate::simple_vehicle::SimpleVehicle;
+use crate::vector_space::VectorSpace;
+
+// lengths in world units, the circle is in the vehicle's local space so it turns with it
+pub struct Wander<V: VectorSpace> {
+    pub radius: f32,
+    // how far ahead along forward the circle's center is
+    pub distance: f32,
+    // how far the target may drift per second before it is pulled back onto the circle
+    pub jitter_rate: f32,
+    // local offset from the circle's center, always radius long
+    target: V,
+    rng: SimRng,
+}
+
+impl<V: VectorSpace> Wander<V> {
+    // forks its own rng from the simulation's, so the same seed wanders the same way
+    pub fn new(radius: f32, distance: f32, jitter_rate: f32, rng: &mut SimRng) -> Self {
+        let mut rng = random::fork(rng);
+        Wander {
+            radius,
+            distance,
+            jitter_rate,
+            target: V::random_unit_vector(&mut rng) * radius,
+            rng,
+        }
+    }
+
+    // local offset of the wander target from the circle's center, the displacement
+    pub fn displacement(&self) -> V {
+        self.target
+    }
+
+    pub fn circle_center(&self, vehicle: &SimpleVehicle<V>) -> V {
+        vehicle.local_space.globalize_position(V::FORWARD * self.distance)
+    }
+
+    // world position of the point being steered for
+    pub fn target_position(&self, vehicle: &SimpleVehicle<V>) -> V {
+        vehicle.local_space.globalize_position(V::FORWARD * self.distance + self.target)
+    }
+}
+
+impl<V: VectorSpace> SteeringBehavior<V> for Wander<V> {
+    fn name(&self) -> &'static str {
+        "wander"
+    }
+
+    // jitters the target by the time step, then heads for it at max speed
+    fn steering_force(&mut self, vehicle: &SimpleVehicle<V>, world: &World<V>) -> V {
+        let jitter = V::gen_random_vector(&mut self.rng) * (self.jitter_rate * world.time_step);
+        let target = (self.target + jitter).normalized();
+        // a jitter that exactly cancels keeps the old target
+        if target != V::ZERO {
+            self.target = target * self.radius;
+        }
+
+        let offset = self.target_position(vehicle) - vehicle.local_space.position;
+        steer_towards_velocity(vehicle, offset.normalized() * vehicle.params.max_speed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_mode::MathMode;
+    use crate::simple_vehicle::REFERENCE_TIME_STEP;
+    use crate::vector::Vector;
+    use crate::vector2d::Vector2D;
+
+    fn wander_path<V: VectorSpace>(seed: u64, start: V) -> Vec<V> {
+        let mut sim_rng = random::seeded_rng(seed);
+        let mut wander = Wander::new(20.0, 40.0, 150.0, &mut sim_rng);
+        let mut vehicle = SimpleVehicle::new(start, MathMode::Exact);
+        let world = World::new(V::ZERO, REFERENCE_TIME_STEP);
+        (0..300)
+            .map(|_| {
+                let force = wander.steering_force(&vehicle, &world);
+                vehicle.apply_global_force(force);
+                vehicle.update(REFERENCE_TIME_STEP);
+
+                let on_circle = wander.target_position(&vehicle).distance(wander.circle_center(&vehicle));
+                assert!((on_circle - 20.0).abs() < 1.0e-3);
+                vehicle.local_space.position
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_wanders_the_same_way() {
+        assert_eq!(wander_path(3, Vector2D::ZERO), wander_path(3, Vector2D::ZERO));
+        assert_ne!(wander_path(3, Vector2D::ZERO), wander_path(4, Vector2D::ZERO));
+        assert_eq!(wander_path(3, Vector::ZERO), wander_path(3, Vector::ZERO));
+    }
+}
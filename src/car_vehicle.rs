@@ -0,0 +1,189 @@
+// car_vehicle.rs
+// car-like vehicle for the 2D demos, moves by a kinematic bicycle model instead of free forces
+use crate::math_mode::MathMode;
+use crate::simple_vehicle::SimpleVehicle;
+use crate::vector2d::Vector2D;
+use crate::vehicle_params::VehicleParams;
+
+// lengths in world units, angles in radians
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CarParams {
+    // distance between the axles, with max_steering_angle it sets the minimum turning radius
+    pub wheelbase: f32,
+    pub max_steering_angle: f32,
+    // caps how hard the car turns at speed, in world units per second squared
+    pub max_lateral_acceleration: f32,
+}
+
+impl CarParams {
+    // turning radius at full lock
+    pub fn min_turning_radius(&self) -> f32 {
+        self.wheelbase / self.max_steering_angle.tan()
+    }
+
+    // picks the max steering angle that gives this turning radius for the current wheelbase
+    pub fn set_min_turning_radius(&mut self, radius: f32) {
+        assert!(radius > 0.0, "min turning radius must be positive, got {}", radius);
+        self.max_steering_angle = (self.wheelbase / radius).atan();
+    }
+}
+
+impl Default for CarParams {
+    // sized for the demo preset, about twice the vehicle radius to turn around
+    fn default() -> Self {
+        CarParams {
+            wheelbase: 10.0,
+            max_steering_angle: 35.0_f32.to_radians(),
+            max_lateral_acceleration: 300.0,
+        }
+    }
+}
+
+// the commands the last update turned the steering force into
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct CarControls {
+    // share of max_force along forward, negative brakes, -1.0 to 1.0
+    pub throttle: f32,
+    // front wheel angle, positive turns towards side
+    pub steering_angle: f32,
+}
+
+// speed and heading are the state, velocity always points along forward so there is no side slip.
+// forces applied to the inner vehicle become throttle and steering instead of moving it directly
+pub struct CarVehicle {
+    pub vehicle: SimpleVehicle<Vector2D>,
+    pub car: CarParams,
+    pub controls: CarControls,
+    // never negative, the car does not reverse
+    pub speed: f32,
+}
+
+impl CarVehicle {
+    pub fn new(position: Vector2D, math_mode: MathMode, params: VehicleParams, car: CarParams) -> Self {
+        CarVehicle {
+            vehicle: SimpleVehicle::with_params(position, math_mode, params),
+            car,
+            controls: CarControls::default(),
+            speed: 0.0,
+        }
+    }
+
+    // same as on SimpleVehicle, so steering from the existing behaviors can be applied unchanged
+    pub fn apply_global_force(&mut self, force: Vector2D) {
+        self.vehicle.apply_global_force(force);
+    }
+
+    // splits the force into along forward (throttle) and across it (steering).
+    // the steering angle is the one whose turn gives the requested lateral acceleration,
+    // limited by the wheel lock and by max_lateral_acceleration at the current speed
+    pub fn controls_for_force(&self, force: Vector2D) -> CarControls {
+        let params = &self.vehicle.params;
+        let force = self.vehicle.math.truncate(force, params.max_force);
+        let local = self.vehicle.local_space.localize_direction(force);
+        let (lateral, longitudinal) = (local.x, local.y);
+
+        let throttle = if params.max_force > 0.0 { (longitudinal / params.max_force).clamp(-1.0, 1.0) } else { 0.0 };
+
+        // a lateral force at a standstill asks for full lock
+        let speed_squared = self.speed * self.speed;
+        let requested = if speed_squared > 0.0 {
+            (self.car.wheelbase * (lateral / params.mass) / speed_squared).atan()
+        } else {
+            self.car.max_steering_angle.copysign(lateral)
+        };
+        let max_angle = self.max_steering_angle_at_speed();
+        let steering_angle = if lateral == 0.0 { 0.0 } else { requested.clamp(-max_angle, max_angle) };
+
+        CarControls { throttle, steering_angle }
+    }
+
+    // the wheel lock, or less when turning that hard would exceed max_lateral_acceleration
+    pub fn max_steering_angle_at_speed(&self) -> f32 {
+        let speed_squared = self.speed * self.speed;
+        if speed_squared > 0.0 {
+            let grip_limit = (self.car.wheelbase * self.car.max_lateral_acceleration / speed_squared).atan();
+            grip_limit.min(self.car.max_steering_angle)
+        } else {
+            self.car.max_steering_angle
+        }
+    }
+
+    // advances the car by dt seconds
+    pub fn update(&mut self, dt: f32) {
+        let force = self.vehicle.all_forces;
+        self.vehicle.all_forces = Vector2D::ZERO;
+        self.controls = self.controls_for_force(force);
+
+        let params = self.vehicle.params;
+        let old_velocity = self.vehicle.velocity;
+        self.speed = (self.speed + self.controls.throttle * params.max_force / params.mass * dt).clamp(0.0, params.max_speed);
+
+        // bicycle model, turning towards side is clockwise so the heading angle goes down,
+        // the position follows the heading halfway through the turn
+        let yaw_rate = self.speed * self.controls.steering_angle.tan() / self.car.wheelbase;
+        let space = &mut self.vehicle.local_space;
+        let heading = space.heading_angle();
+        let mid_heading = heading - 0.5 * yaw_rate * dt;
+        space.position += Vector2D::new(mid_heading.cos(), mid_heading.sin()) * (self.speed * dt);
+        space.set_heading_angle(heading - yaw_rate * dt);
+
+        self.vehicle.velocity = space.forward * self.speed;
+        let acceleration = if dt > 0.0 { (self.vehicle.velocity - old_velocity) / dt } else { Vector2D::ZERO };
+        self.vehicle.acceleration = acceleration;
+        self.vehicle.annotate_path(acceleration, dt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple_vehicle::REFERENCE_TIME_STEP;
+
+    fn car() -> CarVehicle {
+        CarVehicle::new(Vector2D::ZERO, MathMode::Exact, VehicleParams::demo(), CarParams::default())
+    }
+
+    #[test]
+    fn full_lock_turns_at_min_radius_without_slip() {
+        let mut car = car();
+        car.car.max_lateral_acceleration = f32::INFINITY;
+        car.speed = 10.0;
+        for _ in 0..240 {
+            let side = car.vehicle.local_space.side;
+            car.apply_global_force(side * car.vehicle.params.max_force);
+            car.update(REFERENCE_TIME_STEP);
+            assert!(car.vehicle.velocity.dot(car.vehicle.local_space.side).abs() < 1.0e-3);
+        }
+        assert!((car.controls.steering_angle - car.car.max_steering_angle).abs() < 1.0e-6);
+        let radius = car.car.min_turning_radius();
+        assert!((car.vehicle.curvature - 1.0 / radius).abs() < 1.0e-3, "curvature {}", car.vehicle.curvature);
+    }
+
+    #[test]
+    fn lateral_acceleration_limits_steering_at_speed() {
+        let mut car = car();
+        car.car.max_lateral_acceleration = 50.0;
+        car.speed = car.vehicle.params.max_speed;
+        let controls = car.controls_for_force(car.vehicle.local_space.side * car.vehicle.params.max_force);
+        let turn_acceleration = car.speed * car.speed * controls.steering_angle.tan() / car.car.wheelbase;
+        assert!(controls.steering_angle < car.car.max_steering_angle);
+        assert!(turn_acceleration <= car.car.max_lateral_acceleration * (1.0 + 1.0e-4));
+    }
+
+    #[test]
+    fn forces_become_throttle_and_braking() {
+        let mut car = car();
+        let max_force = car.vehicle.params.max_force;
+        let forward = car.vehicle.local_space.forward;
+        car.apply_global_force(forward * max_force * 0.5);
+        car.update(REFERENCE_TIME_STEP);
+        assert!((car.controls.throttle - 0.5).abs() < 1.0e-6);
+        assert!(car.speed > 0.0);
+
+        for _ in 0..600 {
+            car.apply_global_force(-forward * max_force);
+            car.update(REFERENCE_TIME_STEP);
+        }
+        assert_eq!(car.speed, 0.0);
+    }
+}
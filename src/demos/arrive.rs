@@ -0,0 +1,119 @@
+// demos/arrive.rs
+// a vehicle arrives at the target and stops, then the target moves somewhere else
+use ggez::graphics;
+use ggez::input::keyboard::KeyCode;
+use ggez::{Context, GameResult};
+
+use boids::agent::Agent;
+use boids::behaviors::{Arrive, ArriveProfile, World};
+use boids::random::{self, SimRng};
+use boids::simple_vehicle::SimpleVehicle;
+use boids::vector2d::Vector2D;
+use boids::vector_space::VectorSpace;
+
+use crate::demos::{draw_target, new_vehicle, view_center, Demo};
+use crate::Options;
+
+const SLOWING_RADIUS: f32 = 80.0;
+// counts as arrived inside this distance and below this speed
+const ARRIVED_DISTANCE: f32 = 0.5;
+const ARRIVED_SPEED: f32 = 0.5;
+// fixed steps to rest on the target before it moves, one second
+const REST_STEPS: u32 = 60;
+
+const PROFILES: [ArriveProfile; 3] = [ArriveProfile::Linear, ArriveProfile::Quadratic, ArriveProfile::EaseOut];
+
+pub struct ArriveDemo {
+    target_position: Vector2D,
+    agent: Agent<Vector2D>,
+    arrive: Arrive,
+    steps_at_rest: u32,
+    rng: SimRng,
+}
+
+impl ArriveDemo {
+    pub fn new(options: &Options) -> ArriveDemo {
+        let vehicle = new_vehicle(options);
+        let arrive = Arrive::new(SLOWING_RADIUS, ArriveProfile::default());
+        ArriveDemo {
+            target_position: view_center(),
+            agent: Agent::new(vehicle).with_behavior(arrive),
+            arrive,
+            steps_at_rest: 0,
+            rng: random::seeded_rng(options.seed),
+        }
+    }
+
+    fn random_point(&mut self) -> Vector2D {
+        view_center() + Vector2D::gen_random_vector(&mut self.rng) * 200.0
+    }
+
+    // rebuilds the agent's behavior after the profile changed
+    fn set_profile(&mut self, profile: ArriveProfile) {
+        self.arrive.profile = profile;
+        self.agent.behaviors.clear();
+        self.agent.behaviors.push(Box::new(self.arrive));
+    }
+}
+
+impl Demo for ArriveDemo {
+    fn vehicles(&self) -> Vec<&SimpleVehicle<Vector2D>> {
+        vec![&self.agent.vehicle]
+    }
+
+    fn reset(&mut self) {
+        self.target_position = self.random_point();
+        let position = self.random_point();
+        let unit_random = Vector2D::gen_random_vector(&mut self.rng);
+        let vehicle = &mut self.agent.vehicle;
+        vehicle.local_space.position = position;
+        vehicle.velocity = unit_random * vehicle.params.max_speed;
+        vehicle.reset_smoothing();
+        self.steps_at_rest = 0;
+    }
+
+    fn step(&mut self, dt: f32) {
+        let world = World::new(self.target_position, dt);
+        self.agent.update(&world, dt);
+
+        let vehicle = &self.agent.vehicle;
+        let arrived = vehicle.local_space.position.distance(self.target_position) <= ARRIVED_DISTANCE
+            && vehicle.velocity.length() <= ARRIVED_SPEED;
+        self.steps_at_rest = if arrived { self.steps_at_rest + 1 } else { 0 };
+        if self.steps_at_rest > REST_STEPS {
+            self.target_position = self.random_point();
+            self.steps_at_rest = 0;
+        }
+    }
+
+    fn draw(&self, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult {
+        let slowing_circle = graphics::Mesh::new_circle(
+            ctx,
+            graphics::DrawMode::stroke(1.0),
+            self.target_position,
+            self.arrive.slowing_radius,
+            0.5,
+            graphics::Color::from_rgb(150, 150, 110),
+        )?;
+        canvas.draw(&slowing_circle, graphics::DrawParam::default());
+        draw_target(ctx, canvas, self.target_position.into())?;
+        self.agent.draw(ctx, canvas, graphics::Color::from_rgb(128, 192, 255))
+    }
+
+    fn status(&self) -> String {
+        let vehicle = &self.agent.vehicle;
+        format!(
+            "arrive profile: {} (P to change)\ndistance: {:.2}  speed: {:.2}",
+            self.arrive.profile.name(),
+            vehicle.local_space.position.distance(self.target_position),
+            vehicle.velocity.length()
+        )
+    }
+
+    fn key_pressed(&mut self, key: KeyCode) {
+        if key == KeyCode::P {
+            let index = PROFILES.iter().position(|&profile| profile == self.arrive.profile).unwrap_or(0);
+            self.set_profile(PROFILES[(index + 1) % PROFILES.len()]);
+        }
+    }
+}
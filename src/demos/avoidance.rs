@@ -0,0 +1,163 @@
+// demos/avoidance.rs
+// a seeker crosses a field of round obstacles that can be dragged about with the mouse
+use ggez::glam::Vec2;
+use ggez::graphics;
+use ggez::{Context, GameResult};
+use rand::Rng;
+
+use boids::agent::Agent;
+use boids::behaviors::{ObstacleAvoidance, Prioritized, Seek, World};
+use boids::obstacle::SphereObstacle;
+use boids::random::{self, SimRng};
+use boids::simple_vehicle::SimpleVehicle;
+use boids::vector2d::Vector2D;
+use boids::vector_space::VectorSpace;
+
+use crate::demos::{draw_target, new_vehicle, view_center, Demo};
+use crate::Options;
+
+const OBSTACLES: usize = 6;
+const MIN_OBSTACLE_RADIUS: f32 = 20.0;
+const MAX_OBSTACLE_RADIUS: f32 = 45.0;
+const LOOKAHEAD_TIME: f32 = 1.5;
+// tries at placing a target outside every obstacle before taking the last one
+const TARGET_TRIES: usize = 20;
+
+pub struct AvoidanceDemo {
+    target_position: Vector2D,
+    agent: Agent<Vector2D>,
+    avoidance: ObstacleAvoidance,
+    obstacles: Vec<SphereObstacle<Vector2D>>,
+    // index of the obstacle being dragged and the grab point's offset from its center
+    dragging: Option<(usize, Vector2D)>,
+    rng: SimRng,
+}
+
+impl AvoidanceDemo {
+    pub fn new(options: &Options) -> AvoidanceDemo {
+        let vehicle = new_vehicle(options);
+        let avoidance = ObstacleAvoidance::new(LOOKAHEAD_TIME);
+        // avoidance first, seek only steers while nothing is in the way
+        let steering = Prioritized::new().with_behavior(avoidance).with_behavior(Seek::new());
+        AvoidanceDemo {
+            target_position: view_center(),
+            agent: Agent::new(vehicle).with_behavior(steering),
+            avoidance,
+            obstacles: Vec::new(),
+            dragging: None,
+            rng: random::seeded_rng(options.seed),
+        }
+    }
+
+    fn random_point(&mut self) -> Vector2D {
+        view_center() + Vector2D::gen_random_vector(&mut self.rng) * 220.0
+    }
+
+    // somewhere clear of the obstacles, the seeker could never touch a target inside one
+    fn random_clear_point(&mut self) -> Vector2D {
+        let mut point = self.random_point();
+        for _ in 0..TARGET_TRIES {
+            if !self.obstacles.iter().any(|obstacle| obstacle.contains(point)) {
+                break;
+            }
+            point = self.random_point();
+        }
+        point
+    }
+}
+
+impl Demo for AvoidanceDemo {
+    fn vehicles(&self) -> Vec<&SimpleVehicle<Vector2D>> {
+        vec![&self.agent.vehicle]
+    }
+
+    fn reset(&mut self) {
+        self.obstacles = (0..OBSTACLES)
+            .map(|_| {
+                let center = view_center() + Vector2D::gen_random_vector(&mut self.rng) * 160.0;
+                let radius = self.rng.gen_range(MIN_OBSTACLE_RADIUS..MAX_OBSTACLE_RADIUS);
+                SphereObstacle::new(center, radius)
+            })
+            .collect();
+        self.dragging = None;
+
+        let position = self.random_clear_point();
+        let unit_random = Vector2D::random_unit_vector(&mut self.rng);
+        self.target_position = self.random_clear_point();
+        let vehicle = &mut self.agent.vehicle;
+        vehicle.local_space.position = position;
+        vehicle.local_space.regenerate_basis(unit_random, None);
+        vehicle.velocity = unit_random * vehicle.params.max_speed;
+        vehicle.reset_smoothing();
+    }
+
+    fn step(&mut self, dt: f32) {
+        let world = World::new(self.target_position, dt).with_obstacles(&self.obstacles);
+        self.agent.update(&world, dt);
+
+        // a new target as soon as the seeker gets there
+        let vehicle = &self.agent.vehicle;
+        if vehicle.local_space.position.distance(self.target_position) <= vehicle.params.radius {
+            self.target_position = self.random_clear_point();
+        }
+    }
+
+    fn draw(&self, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult {
+        let vehicle = &self.agent.vehicle;
+        let world = World::new(self.target_position, 0.0).with_obstacles(&self.obstacles);
+        let threat = self.avoidance.nearest_threat(vehicle, &world).map(|(obstacle, _)| obstacle);
+
+        for (index, obstacle) in self.obstacles.iter().enumerate() {
+            // the one being dragged is darker, the one being avoided is outlined in red
+            let fill = if self.dragging.is_some_and(|(dragged, _)| dragged == index) {
+                graphics::Color::from_rgb(150, 150, 130)
+            } else {
+                graphics::Color::from_rgb(190, 190, 170)
+            };
+            let is_threat = threat.is_some_and(|threat| std::ptr::eq(threat, obstacle));
+            let outline = if is_threat { graphics::Color::RED } else { graphics::Color::BLACK };
+            let body = graphics::Mesh::new_circle(ctx, graphics::DrawMode::fill(), obstacle.center, obstacle.radius, 0.5, fill)?;
+            let border = graphics::Mesh::new_circle(ctx, graphics::DrawMode::stroke(2.0), obstacle.center, obstacle.radius, 0.5, outline)?;
+            canvas.draw(&body, graphics::DrawParam::default());
+            canvas.draw(&border, graphics::DrawParam::default());
+        }
+
+        // how far ahead the avoidance looks
+        let start: Vec2 = vehicle.local_space.position.into();
+        let end: Vec2 = (vehicle.local_space.position + vehicle.local_space.forward * self.avoidance.lookahead_distance(vehicle)).into();
+        let sweep = graphics::Mesh::new_line(ctx, &[start, end], 1.0, graphics::Color::from_rgb(120, 120, 90))?;
+        canvas.draw(&sweep, graphics::DrawParam::default());
+
+        draw_target(ctx, canvas, self.target_position.into())?;
+        self.agent.draw(ctx, canvas, graphics::Color::from_rgb(128, 255, 128))
+    }
+
+    fn status(&self) -> String {
+        format!("drag the obstacles with the mouse, lookahead {} s", LOOKAHEAD_TIME)
+    }
+
+    fn mouse_pressed(&mut self, position: Vector2D) {
+        // the topmost, last drawn obstacle wins
+        self.dragging = self
+            .obstacles
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, obstacle)| obstacle.contains(position))
+            .map(|(index, obstacle)| (index, obstacle.center - position));
+    }
+
+    fn mouse_moved(&mut self, position: Vector2D) {
+        if let Some((index, grab_offset)) = self.dragging {
+            self.obstacles[index].center = position + grab_offset;
+            // the seeker could never reach a target the obstacle was dropped on
+            if self.obstacles[index].contains(self.target_position) {
+                self.target_position = self.random_clear_point();
+            }
+        }
+    }
+
+    fn mouse_released(&mut self) {
+        self.dragging = None;
+    }
+}
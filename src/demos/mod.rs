@@ -0,0 +1,140 @@
+// demos/mod.rs
+// the scenes the demo window can show, part of the binary rather than the library
+use ggez::glam::Vec2;
+use ggez::graphics;
+use ggez::input::keyboard::KeyCode;
+use ggez::{Context, GameResult};
+
+use boids::behaviors::{Seek, SteeringBehavior, World};
+use boids::integrator::integrator_from_name;
+use boids::simple_vehicle::SimpleVehicle;
+use boids::vector2d::Vector2D;
+use boids::vector_space::VectorSpace;
+
+use crate::{Options, HEIGHT, WIDTH};
+
+mod arrive;
+mod avoidance;
+mod pursuit;
+mod seek_flee;
+mod wander;
+
+pub const SCALE: f32 = 15.0;
+
+// one scene, stepped at the fixed rate by main and drawn every frame
+pub trait Demo {
+    // what the HUD shows above the demo's own status
+    fn vehicles(&self) -> Vec<&SimpleVehicle<Vector2D>>;
+
+    // random starts come from the demo's rng, so a reset is replayed by the same seed
+    fn reset(&mut self);
+
+    // one fixed step of dt seconds
+    fn step(&mut self, dt: f32);
+
+    fn draw(&self, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult;
+
+    // extra HUD lines
+    fn status(&self) -> String;
+
+    // keys main does not use itself
+    fn key_pressed(&mut self, _key: KeyCode) {}
+
+    // left mouse button, positions are in window pixels like the world
+    fn mouse_pressed(&mut self, _position: Vector2D) {}
+
+    fn mouse_moved(&mut self, _position: Vector2D) {}
+
+    fn mouse_released(&mut self) {}
+}
+
+// in the order of the number keys that select them
+pub const DEMO_NAMES: [&str; 5] = ["seek-flee", "arrive", "pursuit", "wander", "avoidance"];
+
+// builds the scene called name, one of DEMO_NAMES as given to --demo
+pub fn demo_from_name(name: &str, options: &Options) -> Option<Box<dyn Demo>> {
+    match name {
+        "seek-flee" => Some(Box::new(seek_flee::SeekFlee::new(options))),
+        "arrive" => Some(Box::new(arrive::ArriveDemo::new(options))),
+        "pursuit" => Some(Box::new(pursuit::PursuitDemo::new(options))),
+        "wander" => Some(Box::new(wander::WanderDemo::new(options))),
+        "avoidance" => Some(Box::new(avoidance::AvoidanceDemo::new(options))),
+        _ => None,
+    }
+}
+
+// restarts a scene a quarter second after the first touch, what seek/flee always did
+#[derive(Default)]
+pub struct TouchReset {
+    touched: bool,
+    steps_since_touch: u32,
+}
+
+impl TouchReset {
+    // counted in fixed steps, so this is a quarter second at any frame rate
+    const STEPS: u32 = 15;
+
+    // call once per step, true when the scene should reset. the touch sticks until then
+    pub fn update(&mut self, touching: bool) -> bool {
+        self.touched |= touching;
+        self.steps_since_touch = if self.touched { self.steps_since_touch + 1 } else { 0 };
+        if self.steps_since_touch > TouchReset::STEPS {
+            *self = TouchReset::default();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = TouchReset::default();
+    }
+}
+
+// seeks back to the middle of the window once a vehicle strays too far, keeps wanderers on screen
+pub struct StayInView {
+    pub radius: f32,
+}
+
+impl SteeringBehavior<Vector2D> for StayInView {
+    fn name(&self) -> &'static str {
+        "stay-in-view"
+    }
+
+    fn steering_force(&mut self, vehicle: &SimpleVehicle<Vector2D>, world: &World<Vector2D>) -> Vector2D {
+        if vehicle.local_space.position.distance(view_center()) > self.radius {
+            Seek::new().steering_force(vehicle, &World::new(view_center(), world.time_step))
+        } else {
+            Vector2D::ZERO
+        }
+    }
+}
+
+pub fn view_center() -> Vector2D {
+    Vector2D::new(WIDTH * 0.5, HEIGHT * 0.5)
+}
+
+// a vehicle with the tuning and integrator picked on the command line
+pub fn new_vehicle(options: &Options) -> SimpleVehicle<Vector2D> {
+    let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, options.math_mode, options.params);
+    // names were checked when the arguments were parsed
+    vehicle.integrator = integrator_from_name(&options.integrator).expect("integrator name was validated");
+    vehicle
+}
+
+// crosshair in a circle, sized from SCALE
+pub fn draw_target(ctx: &mut Context, canvas: &mut graphics::Canvas, position: Vec2) -> GameResult {
+    let diameter = SCALE - 1.0;
+    let radius = SCALE * 0.5;
+
+    let horizontal = [position - Vec2::new(diameter, 0.0), position + Vec2::new(diameter, 0.0)];
+    let vertical = [position - Vec2::new(0.0, diameter), position + Vec2::new(0.0, diameter)];
+    let horizontal_line = graphics::Mesh::new_line(ctx, &horizontal, 2.0, graphics::Color::BLACK)?;
+    let vertical_line = graphics::Mesh::new_line(ctx, &vertical, 2.0, graphics::Color::BLACK)?;
+    let circle = graphics::Mesh::new_circle(ctx, graphics::DrawMode::stroke(2.0), position, radius, 0.2, graphics::Color::BLACK)?;
+
+    canvas.draw(&circle, graphics::DrawParam::default());
+    canvas.draw(&horizontal_line, graphics::DrawParam::default());
+    canvas.draw(&vertical_line, graphics::DrawParam::default());
+    Ok(())
+}
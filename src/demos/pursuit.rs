@@ -0,0 +1,136 @@
+// demos/pursuit.rs
+// a pursuer chases a wandering quarry, the quarry starts somewhere else once it is caught
+use ggez::graphics;
+use ggez::input::keyboard::KeyCode;
+use ggez::{Context, GameResult};
+
+use boids::agent::Agent;
+use boids::behaviors::{Evade, Pursue, Wander, World};
+use boids::random::{self, SimRng};
+use boids::simple_vehicle::SimpleVehicle;
+use boids::vector2d::Vector2D;
+use boids::vector_space::VectorSpace;
+
+use crate::demos::{draw_target, new_vehicle, view_center, Demo, StayInView, TouchReset};
+use crate::Options;
+
+// seconds, keeps the lead point on screen when the quarry is far away
+const MAX_PREDICTION_TIME: f32 = 2.0;
+// the quarry is slower so it can be caught
+const QUARRY_SPEED_SHARE: f32 = 0.6;
+// the quarry's wander circle and how far from the middle it may stray
+const WANDER_RADIUS: f32 = 20.0;
+const WANDER_DISTANCE: f32 = 40.0;
+const JITTER_RATE: f32 = 150.0;
+const VIEW_RADIUS: f32 = 230.0;
+
+pub struct PursuitDemo {
+    pursuer: Agent<Vector2D>,
+    quarry: Agent<Vector2D>,
+    pursue: Pursue,
+    // E toggles evade on the quarry
+    quarry_evades: bool,
+    touch_reset: TouchReset,
+    rng: SimRng,
+}
+
+impl PursuitDemo {
+    pub fn new(options: &Options) -> PursuitDemo {
+        let pursue = Pursue::new(Some(MAX_PREDICTION_TIME));
+        let mut quarry = new_vehicle(options);
+        quarry.params = quarry.params.to_builder().max_speed(quarry.params.max_speed * QUARRY_SPEED_SHARE).build();
+        let mut demo = PursuitDemo {
+            pursuer: Agent::new(new_vehicle(options)).with_behavior(pursue),
+            quarry: Agent::new(quarry),
+            pursue,
+            quarry_evades: false,
+            touch_reset: TouchReset::default(),
+            rng: random::seeded_rng(options.seed),
+        };
+        demo.set_quarry_evades(false);
+        demo
+    }
+
+    fn random_point(&mut self) -> Vector2D {
+        view_center() + Vector2D::gen_random_vector(&mut self.rng) * 200.0
+    }
+
+    // new random start for the quarry, the pursuer carries on from where it is
+    fn reset_quarry(&mut self) {
+        let position = self.random_point();
+        let unit_random = Vector2D::random_unit_vector(&mut self.rng);
+        let quarry = &mut self.quarry.vehicle;
+        quarry.local_space.position = position;
+        quarry.velocity = unit_random * quarry.params.max_speed;
+        quarry.reset_smoothing();
+        self.touch_reset.clear();
+    }
+
+    fn set_quarry_evades(&mut self, evades: bool) {
+        self.quarry_evades = evades;
+        self.quarry.behaviors.clear();
+        self.quarry.behaviors.push(Box::new(Wander::new(WANDER_RADIUS, WANDER_DISTANCE, JITTER_RATE, &mut self.rng)));
+        self.quarry.behaviors.push(Box::new(StayInView { radius: VIEW_RADIUS }));
+        if evades {
+            self.quarry.behaviors.push(Box::new(Evade::new(Some(MAX_PREDICTION_TIME))));
+        }
+    }
+}
+
+impl Demo for PursuitDemo {
+    fn vehicles(&self) -> Vec<&SimpleVehicle<Vector2D>> {
+        vec![&self.pursuer.vehicle, &self.quarry.vehicle]
+    }
+
+    fn reset(&mut self) {
+        let position = self.random_point();
+        let unit_random = Vector2D::random_unit_vector(&mut self.rng);
+        let pursuer = &mut self.pursuer.vehicle;
+        pursuer.local_space.position = position;
+        pursuer.velocity = unit_random * pursuer.params.max_speed;
+        pursuer.reset_smoothing();
+        self.reset_quarry();
+    }
+
+    fn step(&mut self, dt: f32) {
+        // the quarry moves first, the pursuer then reacts to where it went
+        let quarry_world = World::new(view_center(), dt).with_target_vehicle(&self.pursuer.vehicle);
+        self.quarry.update(&quarry_world, dt);
+        let quarry = &self.quarry.vehicle;
+        let pursuer_world = World::new(quarry.local_space.position, dt).with_target_vehicle(quarry);
+        self.pursuer.update(&pursuer_world, dt);
+
+        // caught once the hulls overlap
+        let (pursuer, quarry) = (&self.pursuer.vehicle, &self.quarry.vehicle);
+        let touching = pursuer.math.distance(pursuer.local_space.position, quarry.local_space.position)
+            <= pursuer.params.radius + quarry.params.radius;
+        if self.touch_reset.update(touching) {
+            self.reset_quarry();
+        }
+    }
+
+    fn draw(&self, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult {
+        // where the pursuer is heading for
+        let lead = self.pursue.predicted_position(&self.pursuer.vehicle, &self.quarry.vehicle);
+        draw_target(ctx, canvas, lead.into())?;
+        self.quarry.draw(ctx, canvas, graphics::Color::from_rgb(255, 200, 80))?;
+        self.pursuer.draw(ctx, canvas, graphics::Color::RED)
+    }
+
+    fn status(&self) -> String {
+        let prediction = self.pursuer.vehicle.local_space.position.distance(
+            self.pursue.predicted_position(&self.pursuer.vehicle, &self.quarry.vehicle),
+        );
+        format!(
+            "quarry evades: {} (E to change)\nlead distance: {:.1}",
+            if self.quarry_evades { "yes" } else { "no" },
+            prediction
+        )
+    }
+
+    fn key_pressed(&mut self, key: KeyCode) {
+        if key == KeyCode::E {
+            self.set_quarry_evades(!self.quarry_evades);
+        }
+    }
+}
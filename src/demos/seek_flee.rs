@@ -0,0 +1,92 @@
+// demos/seek_flee.rs
+// A Demonstration of Seek Flee behavior based on work by Craig Reynolds
+use ggez::graphics;
+use ggez::{Context, GameResult};
+
+use boids::agent::Agent;
+use boids::behaviors::{Flee, Seek, World};
+use boids::random::{self, SimRng};
+use boids::simple_vehicle::SimpleVehicle;
+use boids::vector2d::Vector2D;
+
+use crate::demos::{draw_target, new_vehicle, view_center, Demo, TouchReset};
+use crate::Options;
+
+pub struct SeekFlee {
+    // This is the main state, and references the starting state
+    target_position: Vector2D,
+    // both start from the same place, one seeks the target and the other flees it
+    seek_agent: Agent<Vector2D>,
+    flee_agent: Agent<Vector2D>,
+    // the demo restarts a quarter second after the seeker reaches the target
+    touch_reset: TouchReset,
+    // every random start comes from this rng, so the seed replays the whole run
+    rng: SimRng,
+}
+
+impl SeekFlee {
+    pub fn new(options: &Options) -> SeekFlee {
+        SeekFlee {
+            target_position: view_center(),
+            seek_agent: Agent::new(new_vehicle(options)).with_behavior(Seek::applet()),
+            flee_agent: Agent::new(new_vehicle(options)).with_behavior(Flee::applet()),
+            touch_reset: TouchReset::default(),
+            rng: random::seeded_rng(options.seed),
+        }
+    }
+}
+
+impl Demo for SeekFlee {
+    fn vehicles(&self) -> Vec<&SimpleVehicle<Vector2D>> {
+        vec![&self.seek_agent.vehicle, &self.flee_agent.vehicle]
+    }
+
+    fn reset(&mut self) {
+        // initial position should be something random
+        // set unit random used to generate a random velocity and position
+        let unit_random = Vector2D::gen_random_vector(&mut self.rng);
+        // position scaled by 170.0
+        let seek_vehicle = &mut self.seek_agent.vehicle;
+        seek_vehicle.local_space.position = view_center() + unit_random * 170.0;
+
+        // velocity scaled by max Speed
+        let unit_random = Vector2D::gen_random_vector(&mut self.rng);
+        seek_vehicle.velocity = unit_random * seek_vehicle.params.max_speed;
+        seek_vehicle.reset_smoothing();
+
+        // initialize flee vehicle with identical values
+        let flee_vehicle = &mut self.flee_agent.vehicle;
+        flee_vehicle.local_space.position = seek_vehicle.local_space.position;
+        flee_vehicle.velocity = seek_vehicle.velocity;
+        flee_vehicle.reset_smoothing();
+        self.touch_reset.clear();
+    }
+
+    fn step(&mut self, dt: f32) {
+        // update code
+        let world = World::new(self.target_position, dt);
+        self.seek_agent.update(&world, dt);
+        self.flee_agent.update(&world, dt);
+
+        // the seeker counts as touching once it is within 0.6 of the target
+        let seek_vehicle = &self.seek_agent.vehicle;
+        let touching = seek_vehicle.math.distance(self.target_position, seek_vehicle.local_space.position) <= 0.6;
+        if self.touch_reset.update(touching) {
+            self.reset();
+        }
+    }
+
+    fn draw(&self, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult {
+        draw_target(ctx, canvas, self.target_position.into())?;
+        self.seek_agent.draw(ctx, canvas, graphics::Color::from_rgb(128, 255, 128))?;
+        self.flee_agent.draw(ctx, canvas, graphics::Color::RED)
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "seek curvature: {:.4}  flee curvature: {:.4}",
+            self.seek_agent.vehicle.smoothed_curvature,
+            self.flee_agent.vehicle.smoothed_curvature
+        )
+    }
+}
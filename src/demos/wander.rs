@@ -0,0 +1,113 @@
+// demos/wander.rs
+// a few vehicles wander about, each with its wander circle and displacement drawn
+use ggez::glam::Vec2;
+use ggez::graphics;
+use ggez::{Context, GameResult};
+
+use boids::agent::Agent;
+use boids::behaviors::{SteeringBehavior, Wander, World};
+use boids::random::{self, SimRng};
+use boids::simple_vehicle::SimpleVehicle;
+use boids::vector2d::Vector2D;
+use boids::vector_space::VectorSpace;
+
+use crate::demos::{new_vehicle, view_center, Demo, StayInView};
+use crate::Options;
+
+const WANDERERS: usize = 3;
+const WANDER_RADIUS: f32 = 20.0;
+const WANDER_DISTANCE: f32 = 40.0;
+const JITTER_RATE: f32 = 150.0;
+// past this distance from the middle the wanderers turn back
+const VIEW_RADIUS: f32 = 230.0;
+
+const COLORS: [graphics::Color; WANDERERS] = [
+    graphics::Color { r: 0.5, g: 1.0, b: 0.5, a: 1.0 },
+    graphics::Color { r: 0.5, g: 0.75, b: 1.0, a: 1.0 },
+    graphics::Color { r: 1.0, g: 0.8, b: 0.3, a: 1.0 },
+];
+
+// the wander is kept outside the agent's behavior list so its circle can be drawn
+struct Wanderer {
+    agent: Agent<Vector2D>,
+    wander: Wander<Vector2D>,
+}
+
+pub struct WanderDemo {
+    wanderers: Vec<Wanderer>,
+    // kept so a reset can fork fresh wander rngs
+    rng: SimRng,
+}
+
+impl WanderDemo {
+    pub fn new(options: &Options) -> WanderDemo {
+        let mut rng = random::seeded_rng(options.seed);
+        let wanderers = (0..WANDERERS)
+            .map(|_| Wanderer {
+                agent: Agent::new(new_vehicle(options)).with_behavior(StayInView { radius: VIEW_RADIUS }),
+                wander: Wander::new(WANDER_RADIUS, WANDER_DISTANCE, JITTER_RATE, &mut rng),
+            })
+            .collect();
+        WanderDemo { wanderers, rng }
+    }
+}
+
+impl Demo for WanderDemo {
+    fn vehicles(&self) -> Vec<&SimpleVehicle<Vector2D>> {
+        self.wanderers.iter().map(|wanderer| &wanderer.agent.vehicle).collect()
+    }
+
+    fn reset(&mut self) {
+        for wanderer in self.wanderers.iter_mut() {
+            let position = view_center() + Vector2D::gen_random_vector(&mut self.rng) * 150.0;
+            let unit_random = Vector2D::random_unit_vector(&mut self.rng);
+            let vehicle = &mut wanderer.agent.vehicle;
+            vehicle.local_space.position = position;
+            vehicle.local_space.regenerate_basis(unit_random, None);
+            vehicle.velocity = unit_random * vehicle.params.max_speed;
+            vehicle.reset_smoothing();
+            wanderer.wander = Wander::new(WANDER_RADIUS, WANDER_DISTANCE, JITTER_RATE, &mut self.rng);
+        }
+    }
+
+    fn step(&mut self, dt: f32) {
+        let world = World::new(view_center(), dt);
+        for wanderer in self.wanderers.iter_mut() {
+            let agent = &mut wanderer.agent;
+            let vehicle = &agent.vehicle;
+            let steering = agent
+                .behaviors
+                .iter_mut()
+                .fold(wanderer.wander.steering_force(vehicle, &world), |total, behavior| {
+                    total + behavior.steering_force(vehicle, &world)
+                });
+            agent.steer(steering, dt);
+        }
+    }
+
+    fn draw(&self, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult {
+        for (wanderer, &color) in self.wanderers.iter().zip(COLORS.iter()) {
+            let vehicle = &wanderer.agent.vehicle;
+            let center: Vec2 = wanderer.wander.circle_center(vehicle).into();
+            let target: Vec2 = wanderer.wander.target_position(vehicle).into();
+            let position: Vec2 = vehicle.local_space.position.into();
+
+            // wander circle, the line out to it, and the displacement to the target
+            let circle = graphics::Mesh::new_circle(ctx, graphics::DrawMode::stroke(1.0), center, WANDER_RADIUS, 0.5, graphics::Color::from_rgb(120, 120, 90))?;
+            let reach = graphics::Mesh::new_line(ctx, &[position, center], 1.0, graphics::Color::from_rgb(120, 120, 90))?;
+            let displacement = graphics::Mesh::new_line(ctx, &[center, target], 2.0, graphics::Color::RED)?;
+            let target_dot = graphics::Mesh::new_circle(ctx, graphics::DrawMode::fill(), target, 3.0, 0.2, graphics::Color::RED)?;
+            canvas.draw(&circle, graphics::DrawParam::default());
+            canvas.draw(&reach, graphics::DrawParam::default());
+            canvas.draw(&displacement, graphics::DrawParam::default());
+            canvas.draw(&target_dot, graphics::DrawParam::default());
+
+            wanderer.agent.draw(ctx, canvas, color)?;
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> String {
+        format!("wander radius {} at {} ahead, jitter {}/s", WANDER_RADIUS, WANDER_DISTANCE, JITTER_RATE)
+    }
+}
@@ -0,0 +1,139 @@
+// integrator.rs
+// numerical integrators for vehicle motion, picked per vehicle or per simulation.
+// a vehicle's steering is worked out once per step by its behaviors, so the acceleration is
+// a single value for the whole step. that leaves the one-evaluation Euler methods, higher
+// order ones would need the forces at intermediate states
+use crate::vector_space::VectorSpace;
+
+// position and velocity of a body being integrated
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MotionState<V: VectorSpace> {
+    pub position: V,
+    pub velocity: V,
+}
+
+pub trait Integrator<V: VectorSpace> {
+    fn name(&self) -> &'static str;
+
+    // advances state by dt seconds under acceleration. constrain_velocity, e.g. a speed limit,
+    // is applied to the velocity position is advanced with and to the velocity returned
+    fn step(&self, state: MotionState<V>, dt: f32, acceleration: V, constrain_velocity: &dyn Fn(V) -> V) -> MotionState<V>;
+}
+
+// for models with nothing to constrain, like the harness spring
+pub fn unconstrained<V: VectorSpace>(velocity: V) -> V {
+    velocity
+}
+
+// position moves with the old velocity, first order and gains energy on oscillators
+pub struct ExplicitEuler;
+
+// position moves with the new, constrained velocity, what SimpleVehicle::update always did.
+// also first order, but symplectic so an oscillator's energy stays bounded
+pub struct SemiImplicitEuler;
+
+impl<V: VectorSpace> Integrator<V> for ExplicitEuler {
+    fn name(&self) -> &'static str {
+        "explicit-euler"
+    }
+
+    fn step(&self, state: MotionState<V>, dt: f32, acceleration: V, constrain_velocity: &dyn Fn(V) -> V) -> MotionState<V> {
+        let velocity = constrain_velocity(state.velocity);
+        MotionState {
+            position: state.position + velocity * dt,
+            velocity: constrain_velocity(velocity + acceleration * dt),
+        }
+    }
+}
+
+impl<V: VectorSpace> Integrator<V> for SemiImplicitEuler {
+    fn name(&self) -> &'static str {
+        "semi-implicit-euler"
+    }
+
+    fn step(&self, state: MotionState<V>, dt: f32, acceleration: V, constrain_velocity: &dyn Fn(V) -> V) -> MotionState<V> {
+        let velocity = constrain_velocity(state.velocity + acceleration * dt);
+        MotionState {
+            position: state.position + velocity * dt,
+            velocity,
+        }
+    }
+}
+
+// integrators are boxed per vehicle, Send so vehicles can still be stepped on other threads
+pub type BoxedIntegrator<V> = Box<dyn Integrator<V> + Send>;
+
+pub const INTEGRATOR_NAMES: [&str; 2] = ["explicit-euler", "semi-implicit-euler"];
+
+// one of INTEGRATOR_NAMES, as given to --integrator
+pub fn integrator_from_name<V: VectorSpace>(name: &str) -> Option<BoxedIntegrator<V>> {
+    match name {
+        "explicit-euler" => Some(Box::new(ExplicitEuler)),
+        "semi-implicit-euler" => Some(Box::new(SemiImplicitEuler)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector2d::Vector2D;
+
+    // end position after t seconds of constant acceleration from rest, in steps of dt
+    fn constant_acceleration_end(integrator: &BoxedIntegrator<Vector2D>, acceleration: f32, t: f32, dt: f32) -> f32 {
+        let mut state = MotionState { position: Vector2D::ZERO, velocity: Vector2D::ZERO };
+        for _ in 0..(t / dt).round() as usize {
+            state = integrator.step(state, dt, Vector2D::new(acceleration, 0.0), &unconstrained);
+        }
+        state.position.x
+    }
+
+    #[test]
+    fn constant_acceleration_converges_to_first_order() {
+        let (acceleration, t) = (20.0, 1.0);
+        let exact = acceleration * t * t / 2.0;
+        for name in INTEGRATOR_NAMES {
+            let integrator = integrator_from_name(name).expect("listed integrators have names");
+            // both Euler methods are off by a dt t / 2, one short and one long
+            let coarse_error = (constant_acceleration_end(&integrator, acceleration, t, 1.0 / 60.0) - exact).abs();
+            let fine_error = (constant_acceleration_end(&integrator, acceleration, t, 1.0 / 120.0) - exact).abs();
+            assert!((coarse_error - acceleration * t / 120.0).abs() < 1.0e-3, "{} off by {}", name, coarse_error);
+            assert!((fine_error / coarse_error - 0.5).abs() < 0.01, "{} went from {} to {}", name, coarse_error, fine_error);
+        }
+    }
+
+    #[test]
+    fn spring_follows_the_analytic_solution() {
+        // x'' = -4x from x = 1 at rest is cos(2t)
+        let dt = 1.0 / 60.0;
+        for name in INTEGRATOR_NAMES {
+            let integrator: BoxedIntegrator<Vector2D> = integrator_from_name(name).expect("listed integrators have names");
+            let mut state = MotionState { position: Vector2D::new(1.0, 0.0), velocity: Vector2D::ZERO };
+            let mut worst: f32 = 0.0;
+            for step in 1..=600 {
+                state = integrator.step(state, dt, -state.position * 4.0, &unconstrained);
+                worst = worst.max((state.position.x - (2.0 * step as f32 * dt).cos()).abs());
+            }
+            let energy = 0.5 * state.velocity.length_squared() + 2.0 * state.position.length_squared();
+            if name == "semi-implicit-euler" {
+                assert!(worst < 0.05, "{} strayed {} from cos(2t)", name, worst);
+                assert!((energy - 2.0).abs() < 0.1, "{} energy {}", name, energy);
+            } else {
+                // explicit Euler spirals outwards, the energy grows by (1 + 4 dt^2) every step
+                assert!((energy / 2.0 - (1.0 + 4.0 * dt * dt).powi(600)).abs() < 0.01, "{} energy {}", name, energy);
+            }
+        }
+    }
+
+    #[test]
+    fn constraint_applies_before_position_moves() {
+        let limit = |velocity: Vector2D| velocity.truncated(1.0);
+        let state = MotionState { position: Vector2D::ZERO, velocity: Vector2D::new(3.0, 0.0) };
+        for name in INTEGRATOR_NAMES {
+            let integrator: BoxedIntegrator<Vector2D> = integrator_from_name(name).expect("listed integrators have names");
+            let next = integrator.step(state, 0.5, Vector2D::new(10.0, 0.0), &limit);
+            assert_eq!(next.position, Vector2D::new(0.5, 0.0), "{}", name);
+            assert_eq!(next.velocity, Vector2D::new(1.0, 0.0), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+// integrator_harness.rs
+// headless comparison of the integrators, `cargo run -- --integrator-report` prints it
+use std::fmt::Write;
+
+use crate::integrator::{integrator_from_name, unconstrained, BoxedIntegrator, MotionState, SemiImplicitEuler, INTEGRATOR_NAMES};
+use crate::simple_vehicle::REFERENCE_TIME_STEP;
+use crate::vector2d::Vector2D;
+use crate::vector_space::VectorSpace;
+use crate::vehicle_params::VehicleParams;
+
+const TIME_STEP: f32 = 1.0 / 60.0;
+const DURATION: f32 = 10.0;
+// the reference trajectory is semi-implicit Euler with this many substeps per TIME_STEP
+const REFERENCE_SUBSTEPS: u32 = 100;
+
+// spring pulling towards the origin, with no damping its energy should stay constant
+const SPRING_STIFFNESS: f32 = 4.0;
+
+fn spring_acceleration(state: MotionState<Vector2D>) -> Vector2D {
+    -state.position * SPRING_STIFFNESS
+}
+
+fn spring_energy(state: MotionState<Vector2D>) -> f32 {
+    0.5 * state.velocity.length_squared() + 0.5 * SPRING_STIFFNESS * state.position.length_squared()
+}
+
+// seek towards the origin at max speed, steering the same way the Seek behavior does
+fn seek_acceleration(state: MotionState<Vector2D>, params: &VehicleParams) -> Vector2D {
+    let desired = (-state.position).normalized() * params.max_speed;
+    ((desired - state.velocity) / REFERENCE_TIME_STEP).truncated(params.max_force) / params.mass
+}
+
+fn run<V: VectorSpace>(
+    integrator: &BoxedIntegrator<V>,
+    start: MotionState<V>,
+    dt: f32,
+    steps: u32,
+    acceleration: &dyn Fn(MotionState<V>) -> V,
+    constrain_velocity: &dyn Fn(V) -> V,
+) -> Vec<MotionState<V>> {
+    let mut states = vec![start];
+    let mut state = start;
+    // like a vehicle, the acceleration is evaluated once per step at the state it starts from
+    for _ in 0..steps {
+        state = integrator.step(state, dt, acceleration(state), constrain_velocity);
+        states.push(state);
+    }
+    states
+}
+
+fn integrators() -> Vec<BoxedIntegrator<Vector2D>> {
+    INTEGRATOR_NAMES
+        .iter()
+        .map(|name| integrator_from_name(name).expect("every listed integrator has a name"))
+        .collect()
+}
+
+// energy drift on the spring and trajectory error on a seek run, one line per integrator
+pub fn report() -> String {
+    let steps = (DURATION / TIME_STEP).round() as u32;
+    let mut report = String::new();
+
+    let spring_start = MotionState {
+        position: Vector2D::new(100.0, 0.0),
+        velocity: Vector2D::new(0.0, 50.0),
+    };
+    let start_energy = spring_energy(spring_start);
+    let _ = writeln!(
+        report,
+        "spring, stiffness {} for {} s at {} Hz: energy drift",
+        SPRING_STIFFNESS,
+        DURATION,
+        (1.0 / TIME_STEP).round()
+    );
+    for integrator in integrators() {
+        let states = run(&integrator, spring_start, TIME_STEP, steps, &spring_acceleration, &unconstrained);
+        let end_energy = spring_energy(*states.last().expect("run keeps the start state"));
+        let drift = (end_energy - start_energy) / start_energy;
+        let _ = writeln!(report, "  {:<20} {:>+10.3}%", integrator.name(), drift * 100.0);
+    }
+
+    let params = VehicleParams::demo();
+    let seek_start = MotionState {
+        position: Vector2D::new(-200.0, 120.0),
+        velocity: Vector2D::new(0.0, params.max_speed),
+    };
+    let seek = |state: MotionState<Vector2D>| seek_acceleration(state, &params);
+    // the vehicle's speed limit
+    let speed_limit = |velocity: Vector2D| velocity.truncated(params.max_speed);
+    let reference_integrator: BoxedIntegrator<Vector2D> = Box::new(SemiImplicitEuler);
+    let reference = run(
+        &reference_integrator,
+        seek_start,
+        TIME_STEP / REFERENCE_SUBSTEPS as f32,
+        steps * REFERENCE_SUBSTEPS,
+        &seek,
+        &speed_limit,
+    );
+
+    let _ = writeln!(
+        report,
+        "\nseek towards the origin from ({}, {}) for {} s: deviation from semi-implicit-euler with {} substeps",
+        seek_start.position.x, seek_start.position.y, DURATION, REFERENCE_SUBSTEPS
+    );
+    for integrator in integrators() {
+        let states = run(&integrator, seek_start, TIME_STEP, steps, &seek, &speed_limit);
+        let max_deviation = states
+            .iter()
+            .enumerate()
+            .map(|(step, state)| state.position.distance(reference[step * REFERENCE_SUBSTEPS as usize].position))
+            .fold(0.0, f32::max);
+        let end = states.last().expect("run keeps the start state").position;
+        let _ = writeln!(
+            report,
+            "  {:<20} max {:>9.4} px   end ({:>8.3}, {:>8.3})",
+            integrator.name(),
+            max_deviation,
+            end.x,
+            end.y
+        );
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_covers_every_integrator_in_both_sections() {
+        let report = report();
+        let (spring, seek) = report.split_once("\nseek").expect("report has a seek section");
+        assert!(spring.starts_with("spring"));
+        for name in INTEGRATOR_NAMES {
+            assert!(spring.contains(name), "spring section is missing {}", name);
+            assert!(seek.lines().skip(1).any(|line| line.trim_start().starts_with(name)), "seek section is missing {}", name);
+        }
+    }
+
+    #[test]
+    fn semi_implicit_euler_keeps_the_spring_energy() {
+        let integrator: BoxedIntegrator<Vector2D> = Box::new(SemiImplicitEuler);
+        let start = MotionState { position: Vector2D::new(100.0, 0.0), velocity: Vector2D::new(0.0, 50.0) };
+        let states = run(&integrator, start, TIME_STEP, 600, &spring_acceleration, &unconstrained);
+        let drift = (spring_energy(*states.last().expect("run keeps the start state")) - spring_energy(start)) / spring_energy(start);
+        assert_eq!(states.len(), 601);
+        // it wobbles by about sqrt(stiffness) * dt over a cycle but does not keep growing
+        assert!(drift.abs() < 0.05, "energy drifted by {}", drift);
+    }
+}
@@ -0,0 +1,323 @@
+// interop.rs
+// conversions between our vectors and the nalgebra, glam and mint types used by tooling and ggez
+use ggez::glam::{Affine2, Mat4, Vec2, Vec3, Vec4};
+use ggez::mint;
+use nalgebra::{Isometry2, Isometry3, Matrix3, Point2, Point3, Rotation3, Translation2, Translation3, UnitComplex, UnitQuaternion, Vector2, Vector3};
+
+use crate::local_space::LocalSpace;
+use crate::vector::Vector;
+use crate::vector2d::Vector2D;
+
+// nalgebra
+
+impl From<Vector3<f32>> for Vector {
+    fn from(vec: Vector3<f32>) -> Self {
+        Vector::new(vec.x, vec.y, vec.z)
+    }
+}
+
+impl From<Vector> for Vector3<f32> {
+    fn from(vec: Vector) -> Self {
+        Vector3::new(vec.x, vec.y, vec.z)
+    }
+}
+
+impl From<Point3<f32>> for Vector {
+    fn from(point: Point3<f32>) -> Self {
+        Vector::new(point.x, point.y, point.z)
+    }
+}
+
+impl From<Vector> for Point3<f32> {
+    fn from(vec: Vector) -> Self {
+        Point3::new(vec.x, vec.y, vec.z)
+    }
+}
+
+impl From<Vector2<f32>> for Vector2D {
+    fn from(vec: Vector2<f32>) -> Self {
+        Vector2D::new(vec.x, vec.y)
+    }
+}
+
+impl From<Vector2D> for Vector2<f32> {
+    fn from(vec: Vector2D) -> Self {
+        Vector2::new(vec.x, vec.y)
+    }
+}
+
+impl From<Point2<f32>> for Vector2D {
+    fn from(point: Point2<f32>) -> Self {
+        Vector2D::new(point.x, point.y)
+    }
+}
+
+impl From<Vector2D> for Point2<f32> {
+    fn from(vec: Vector2D) -> Self {
+        Point2::new(vec.x, vec.y)
+    }
+}
+
+// glam
+
+impl From<Vec3> for Vector {
+    fn from(vec: Vec3) -> Self {
+        Vector::new(vec.x, vec.y, vec.z)
+    }
+}
+
+impl From<Vector> for Vec3 {
+    fn from(vec: Vector) -> Self {
+        Vec3::new(vec.x, vec.y, vec.z)
+    }
+}
+
+// drops z, this is the xy plane the demos draw
+impl From<Vector> for Vec2 {
+    fn from(vec: Vector) -> Self {
+        Vec2::new(vec.x, vec.y)
+    }
+}
+
+// back into the xy plane with z = 0
+impl From<Vec2> for Vector {
+    fn from(vec: Vec2) -> Self {
+        Vector::new(vec.x, vec.y, 0.0)
+    }
+}
+
+impl From<Vec2> for Vector2D {
+    fn from(vec: Vec2) -> Self {
+        Vector2D::new(vec.x, vec.y)
+    }
+}
+
+impl From<Vector2D> for Vec2 {
+    fn from(vec: Vector2D) -> Self {
+        Vec2::new(vec.x, vec.y)
+    }
+}
+
+// 2D vectors sit in the xy plane of 3D space
+impl From<Vector2D> for Vector {
+    fn from(vec: Vector2D) -> Self {
+        Vector::new(vec.x, vec.y, 0.0)
+    }
+}
+
+// mint
+
+impl From<mint::Vector3<f32>> for Vector {
+    fn from(vec: mint::Vector3<f32>) -> Self {
+        Vector::new(vec.x, vec.y, vec.z)
+    }
+}
+
+impl From<Vector> for mint::Vector3<f32> {
+    fn from(vec: Vector) -> Self {
+        mint::Vector3 { x: vec.x, y: vec.y, z: vec.z }
+    }
+}
+
+impl From<mint::Point3<f32>> for Vector {
+    fn from(point: mint::Point3<f32>) -> Self {
+        Vector::new(point.x, point.y, point.z)
+    }
+}
+
+impl From<Vector> for mint::Point3<f32> {
+    fn from(vec: Vector) -> Self {
+        mint::Point3 { x: vec.x, y: vec.y, z: vec.z }
+    }
+}
+
+impl From<mint::Vector2<f32>> for Vector2D {
+    fn from(vec: mint::Vector2<f32>) -> Self {
+        Vector2D::new(vec.x, vec.y)
+    }
+}
+
+impl From<Vector2D> for mint::Vector2<f32> {
+    fn from(vec: Vector2D) -> Self {
+        mint::Vector2 { x: vec.x, y: vec.y }
+    }
+}
+
+impl From<mint::Point2<f32>> for Vector2D {
+    fn from(point: mint::Point2<f32>) -> Self {
+        Vector2D::new(point.x, point.y)
+    }
+}
+
+impl From<Vector2D> for mint::Point2<f32> {
+    fn from(vec: Vector2D) -> Self {
+        mint::Point2 { x: vec.x, y: vec.y }
+    }
+}
+
+// LocalSpace as a single transform, local coordinates map to world coordinates.
+// the from_* constructors regenerate the basis so scaled or sheared input still gives an orthonormal frame
+
+impl LocalSpace<Vector2D> {
+    // columns are side, forward and position
+    pub fn to_affine2(&self) -> Affine2 {
+        Affine2::from_cols(self.side.into(), self.forward.into(), self.position.into())
+    }
+
+    pub fn from_affine2(transform: &Affine2) -> Self {
+        let mut space = LocalSpace::new(transform.translation.into());
+        space.regenerate_basis(transform.matrix2.y_axis.into(), None);
+        space
+    }
+
+    // the 2D transform embedded in the xy plane, what ggez DrawParam::transform takes
+    pub fn to_mat4(&self) -> Mat4 {
+        Mat4::from_cols(
+            Vec4::new(self.side.x, self.side.y, 0.0, 0.0),
+            Vec4::new(self.forward.x, self.forward.y, 0.0, 0.0),
+            Vec4::Z,
+            Vec4::new(self.position.x, self.position.y, 0.0, 1.0),
+        )
+    }
+
+    pub fn to_isometry2(&self) -> Isometry2<f32> {
+        Isometry2::from_parts(
+            Translation2::new(self.position.x, self.position.y),
+            UnitComplex::new(self.side.y.atan2(self.side.x)),
+        )
+    }
+
+    pub fn from_isometry2(isometry: &Isometry2<f32>) -> Self {
+        let mut space = LocalSpace::new(isometry.translation.vector.into());
+        space.regenerate_basis((isometry.rotation * Vector2::y()).into(), None);
+        space
+    }
+}
+
+impl From<&LocalSpace<Vector2D>> for Mat4 {
+    fn from(space: &LocalSpace<Vector2D>) -> Self {
+        space.to_mat4()
+    }
+}
+
+impl LocalSpace<Vector> {
+    // columns are side, up, forward and position
+    pub fn to_mat4(&self) -> Mat4 {
+        let side: Vec3 = self.side.into();
+        let up: Vec3 = self.up.into();
+        let forward: Vec3 = self.forward.into();
+        let position: Vec3 = self.position.into();
+        Mat4::from_cols(side.extend(0.0), up.extend(0.0), forward.extend(0.0), position.extend(1.0))
+    }
+
+    pub fn from_mat4(transform: &Mat4) -> Self {
+        let mut space = LocalSpace::new(transform.w_axis.truncate().into());
+        space.regenerate_basis(transform.z_axis.truncate().into(), Some(transform.y_axis.truncate().into()));
+        space
+    }
+
+    pub fn to_isometry3(&self) -> Isometry3<f32> {
+        let rotation = Matrix3::from_columns(&[self.side.into(), self.up.into(), self.forward.into()]);
+        Isometry3::from_parts(
+            Translation3::new(self.position.x, self.position.y, self.position.z),
+            UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(rotation)),
+        )
+    }
+
+    pub fn from_isometry3(isometry: &Isometry3<f32>) -> Self {
+        let mut space = LocalSpace::new(isometry.translation.vector.into());
+        space.regenerate_basis(
+            (isometry.rotation * Vector3::z()).into(),
+            Some((isometry.rotation * Vector3::y()).into()),
+        );
+        space
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame_2d() -> LocalSpace<Vector2D> {
+        let mut space = LocalSpace::new(Vector2D::new(3.0, -4.0));
+        space.set_heading_angle(0.8);
+        space
+    }
+
+    fn frame_3d() -> LocalSpace<Vector> {
+        let mut space = LocalSpace::new(Vector::new(1.0, 2.0, 3.0));
+        space.rotate(0.4, -0.3, 1.1);
+        space
+    }
+
+    #[test]
+    fn vectors_round_trip() {
+        let vec = Vector::new(1.5, -2.0, 3.25);
+        let vec_2d = Vector2D::new(-0.5, 4.0);
+
+        assert_eq!(Vector::from(Vector3::from(vec)), vec);
+        assert_eq!(Vector::from(Point3::from(vec)), vec);
+        assert_eq!(Vector2D::from(Vector2::from(vec_2d)), vec_2d);
+        assert_eq!(Vector2D::from(Point2::from(vec_2d)), vec_2d);
+
+        assert_eq!(Vector::from(Vec3::from(vec)), vec);
+        assert_eq!(Vector2D::from(Vec2::from(vec_2d)), vec_2d);
+        // through Vec2 only the xy plane survives
+        let flat = Vector::new(vec.x, vec.y, 0.0);
+        assert_eq!(Vector::from(Vec2::from(vec)), flat);
+        assert_eq!(Vec2::from(Vector::from(Vec2::new(1.0, 2.0))), Vec2::new(1.0, 2.0));
+        assert_eq!(Vector::from(vec_2d), Vector::new(-0.5, 4.0, 0.0));
+
+        assert_eq!(Vector::from(mint::Vector3::from(vec)), vec);
+        assert_eq!(Vector::from(mint::Point3::from(vec)), vec);
+        assert_eq!(Vector2D::from(mint::Vector2::from(vec_2d)), vec_2d);
+        assert_eq!(Vector2D::from(mint::Point2::from(vec_2d)), vec_2d);
+    }
+
+    #[test]
+    fn transforms_2d_map_local_points_like_globalize() {
+        let space = frame_2d();
+        let local = Vector2D::new(2.0, 5.0);
+        let expected = space.globalize_position(local);
+
+        let affine: Vector2D = space.to_affine2().transform_point2(local.into()).into();
+        let isometry: Vector2D = (space.to_isometry2() * Point2::from(local)).into();
+        let mat4: Vec2 = space.to_mat4().transform_point3(Vec3::new(local.x, local.y, 0.0)).truncate();
+        assert!(affine.approx_eq_eps(expected, 1.0e-4));
+        assert!(isometry.approx_eq_eps(expected, 1.0e-4));
+        assert!(Vector2D::from(mat4).approx_eq_eps(expected, 1.0e-4));
+    }
+
+    #[test]
+    fn transforms_2d_round_trip() {
+        let space = frame_2d();
+        let from_affine = LocalSpace::from_affine2(&space.to_affine2());
+        let from_isometry = LocalSpace::from_isometry2(&space.to_isometry2());
+        for other in [from_affine, from_isometry] {
+            assert!(other.position.approx_eq(space.position));
+            assert!(other.forward.approx_eq(space.forward));
+            assert!(other.side.approx_eq(space.side));
+        }
+    }
+
+    #[test]
+    fn transforms_3d_round_trip() {
+        let space = frame_3d();
+        let local = Vector::new(-2.0, 0.5, 4.0);
+        let expected = space.globalize_position(local);
+
+        let mat4: Vector = space.to_mat4().transform_point3(local.into()).into();
+        let isometry: Vector = (space.to_isometry3() * Point3::from(local)).into();
+        assert!(mat4.approx_eq_eps(expected, 1.0e-4));
+        assert!(isometry.approx_eq_eps(expected, 1.0e-4));
+
+        let from_mat4 = LocalSpace::from_mat4(&space.to_mat4());
+        let from_isometry = LocalSpace::from_isometry3(&space.to_isometry3());
+        for other in [from_mat4, from_isometry] {
+            assert!(other.position.approx_eq(space.position));
+            assert!(other.forward.approx_eq_eps(space.forward, 1.0e-4));
+            assert!(other.side.approx_eq_eps(space.side, 1.0e-4));
+            assert!(other.up.approx_eq_eps(space.up, 1.0e-4));
+        }
+    }
+}
@@ -0,0 +1,19 @@
+// Steering behaviors based on work by Craig Reynolds
+// the demos in main.rs are built on top of these modules
+
+pub mod vector;
+pub mod vector2d;
+pub mod vector_space;
+pub mod interop;
+pub mod math_mode;
+pub mod random;
+pub mod local_space;
+pub mod simple_vehicle;
+pub mod trail;
+pub mod obstacle;
+pub mod car_vehicle;
+pub mod vehicle_params;
+pub mod integrator;
+pub mod integrator_harness;
+pub mod behaviors;
+pub mod agent;
@@ -1,26 +1,287 @@
-// 2D local space for boids
+// local space for boids, generic over 2D (Vector2D) and 3D (Vector)
 use crate::vector::Vector;
+use crate::vector2d::Vector2D;
+use crate::vector_space::VectorSpace;
 
-pub struct LocalSpace {
-    pub forward: Vector, // z direction vector, necessary to do cross product 
-    pub side: Vector, // x direction vector
-    pub up: Vector, // y direction vector
-    
-    pub position: Vector, // vector representing position
+// how far lengths and dot products may drift before a frame counts as not orthonormal
+const ORTHONORMAL_TOLERANCE: f32 = 1.0e-3;
+
+// local coordinates are (side, up, forward) in 3D and (side, forward) in 2D,
+// the transforms assume side, up and forward are orthonormal
+pub struct LocalSpace<V: VectorSpace = Vector> {
+    pub forward: V, // z direction vector in 3D, y in 2D
+    pub side: V, // x direction vector
+    pub up: V, // y direction vector in 3D, always zero in 2D
+
+    pub position: V, // vector representing position
 }
 
-impl LocalSpace {
-    pub fn new(initial_position: Vector) -> Self {
+impl<V: VectorSpace> LocalSpace<V> {
+    pub fn new(initial_position: V) -> Self {
         LocalSpace {
-            forward: Vector::new(0.0, 0.0, 1.0),
-            side: Vector::new(1.0, 0.0, 0.0),
-            up: Vector::new(0.0, 1.0, 0.0),
+            forward: V::FORWARD,
+            side: V::SIDE,
+            up: V::UP,
             position: initial_position,
         }
     }
 
-    // a bunch of other functions you may or may not need, but most likely won't at this stage
-    // pub fn globalize_direction(&self, direction: Vector) -> Vector {
-    //     self.side * direction.x + self.up * direction.y
-    // }
-}
\ No newline at end of file
+    // world direction to local axes, only rotates
+    pub fn localize_direction(&self, direction: V) -> V {
+        V::localize(direction, self.side, self.up, self.forward)
+    }
+
+    // world point to local coordinates relative to position
+    pub fn localize_position(&self, position: V) -> V {
+        self.localize_direction(position - self.position)
+    }
+
+    // another frame's full pose relative to this one, its position and axes in local coordinates,
+    // e.g. a follower's slot and heading relative to its leader
+    pub fn localize_offset(&self, other: &LocalSpace<V>) -> LocalSpace<V> {
+        LocalSpace {
+            forward: self.localize_direction(other.forward),
+            side: self.localize_direction(other.side),
+            up: self.localize_direction(other.up),
+            position: self.localize_position(other.position),
+        }
+    }
+
+    // inverse of localize_offset, a pose relative to this frame back to a world frame
+    pub fn globalize_offset(&self, offset: &LocalSpace<V>) -> LocalSpace<V> {
+        LocalSpace {
+            forward: self.globalize_direction(offset.forward),
+            side: self.globalize_direction(offset.side),
+            up: self.globalize_direction(offset.up),
+            position: self.globalize_position(offset.position),
+        }
+    }
+
+    // local direction back to world axes, only rotates
+    pub fn globalize_direction(&self, direction: V) -> V {
+        V::globalize(direction, self.side, self.up, self.forward)
+    }
+
+    // local point back to a world point, e.g. a follower's slot behind its leader
+    pub fn globalize_position(&self, position: V) -> V {
+        self.position + self.globalize_direction(position)
+    }
+
+    // rebuilds an orthonormal basis looking along new_forward, banking towards up_hint
+    // or the current up when there is none, a zero forward leaves the frame alone
+    pub fn regenerate_basis(&mut self, new_forward: V, up_hint: Option<V>) {
+        let forward = new_forward.normalized();
+        if forward == V::ZERO {
+            return;
+        }
+        self.forward = forward;
+        (self.side, self.up) = V::side_and_up(forward, up_hint.unwrap_or(self.up));
+        self.debug_assert_orthonormal();
+    }
+
+    // turns forward towards direction by at most max_angle radians, the shortest way round,
+    // and returns the angle turned. straight behind turns towards side
+    pub fn turn_towards(&mut self, direction: V, max_angle: f32, up_hint: Option<V>) -> f32 {
+        let target = direction.normalized();
+        if target == V::ZERO {
+            return 0.0;
+        }
+        let angle = self.forward.dot(target).clamp(-1.0, 1.0).acos();
+        let turn = angle.min(max_angle.max(0.0));
+        if turn <= 0.0 {
+            return 0.0;
+        }
+
+        let across = target.perpendicular_component(self.forward).normalized();
+        let across = if across == V::ZERO { self.side } else { across };
+        let (sin, cos) = turn.sin_cos();
+        self.regenerate_basis(self.forward * cos + across * sin, up_hint);
+        turn
+    }
+
+    // true when forward and side (and up in 3D) are unit length and mutually perpendicular
+    pub fn is_orthonormal(&self, tolerance: f32) -> bool {
+        let unit = |vec: V| (vec.length() - 1.0).abs() <= tolerance;
+        let perpendicular = |vec: V, vec2: V| vec.dot(vec2).abs() <= tolerance;
+
+        let planar = unit(self.forward) && unit(self.side) && perpendicular(self.forward, self.side);
+        // 2D frames keep up at zero
+        if V::UP == V::ZERO {
+            planar && self.up == V::ZERO
+        } else {
+            planar && unit(self.up) && perpendicular(self.up, self.forward) && perpendicular(self.up, self.side)
+        }
+    }
+
+    // catches frames that drifted out of shape in debug builds, free in release
+    pub fn debug_assert_orthonormal(&self) {
+        debug_assert!(
+            self.is_orthonormal(ORTHONORMAL_TOLERANCE),
+            "local space is not orthonormal: forward {:?} side {:?} up {:?}",
+            self.forward,
+            self.side,
+            self.up
+        );
+    }
+}
+
+impl LocalSpace<Vector2D> {
+    // heading in radians counterclockwise from the x axis
+    pub fn heading_angle(&self) -> f32 {
+        self.forward.y.atan2(self.forward.x)
+    }
+
+    pub fn set_heading_angle(&mut self, angle: f32) {
+        let (sin, cos) = angle.sin_cos();
+        self.regenerate_basis(Vector2D::new(cos, sin), None);
+    }
+}
+
+impl LocalSpace<Vector> {
+    // rotates the frame about its own axes, counterclockwise by the right hand rule:
+    // yaw about up, then pitch about side, then roll about forward
+    pub fn rotate(&mut self, yaw: f32, pitch: f32, roll: f32) {
+        let forward = self.forward.rotated_about_axis(self.up, yaw);
+        let side = self.side.rotated_about_axis(self.up, yaw);
+
+        let forward = forward.rotated_about_axis(side, pitch);
+        let up = self.up.rotated_about_axis(side, pitch);
+
+        // roll leaves forward alone, side follows from up when the basis is regenerated
+        let up = up.rotated_about_axis(forward, roll);
+
+        // regenerating from the rotated axes stops rounding error piling up over many calls
+        self.regenerate_basis(forward, Some(up));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector2d::Vector2D;
+
+    // a frame rotated off the world axes so the round trips aren't trivial
+    fn rotated_frame() -> LocalSpace<Vector> {
+        let axis = Vector::new(1.0, 2.0, 0.5);
+        let mut space = LocalSpace::new(Vector::new(5.0, -3.0, 2.0));
+        space.forward = space.forward.rotated_about_axis(axis, 0.7);
+        space.side = space.side.rotated_about_axis(axis, 0.7);
+        space.up = space.up.rotated_about_axis(axis, 0.7);
+        space
+    }
+
+    #[test]
+    fn axes_localize_to_unit_components() {
+        let space = rotated_frame();
+        assert!(space.localize_direction(space.side).approx_eq(Vector::new(1.0, 0.0, 0.0)));
+        assert!(space.localize_direction(space.up).approx_eq(Vector::new(0.0, 1.0, 0.0)));
+        assert!(space.localize_direction(space.forward).approx_eq(Vector::new(0.0, 0.0, 1.0)));
+        assert!(space.localize_position(space.position).is_approx_zero());
+    }
+
+    #[test]
+    fn round_trips_3d() {
+        let space = rotated_frame();
+        let point = Vector::new(-7.0, 4.0, 11.0);
+        assert!(space.globalize_position(space.localize_position(point)).approx_eq_eps(point, 1.0e-4));
+        assert!(space.localize_position(space.globalize_position(point)).approx_eq_eps(point, 1.0e-4));
+        assert!(space.globalize_direction(space.localize_direction(point)).approx_eq_eps(point, 1.0e-4));
+    }
+
+    #[test]
+    fn round_trips_2d() {
+        let mut space = LocalSpace::new(Vector2D::new(10.0, 20.0));
+        space.forward = Vector2D::new(0.6, 0.8);
+        space.side = Vector2D::new(0.8, -0.6);
+
+        let point = Vector2D::new(13.0, 24.0);
+        // 5 units straight ahead of the vehicle
+        assert!(space.localize_position(point).approx_eq(Vector2D::new(0.0, 5.0)));
+        assert!(space.globalize_position(space.localize_position(point)).approx_eq(point));
+    }
+
+    #[test]
+    fn regenerated_basis_is_orthonormal() {
+        let mut space = LocalSpace::new(Vector::new(0.0, 0.0, 0.0));
+        space.regenerate_basis(Vector::new(3.0, 1.0, -2.0), Some(Vector::new(0.2, 1.0, 0.1)));
+        assert!(space.is_orthonormal(1.0e-5));
+        assert!(space.forward.approx_eq(Vector::new(3.0, 1.0, -2.0).normalized()));
+
+        // an up hint along forward still gives a usable frame
+        space.regenerate_basis(Vector::new(0.0, 1.0, 0.0), Some(Vector::new(0.0, 1.0, 0.0)));
+        assert!(space.is_orthonormal(1.0e-5));
+
+        let mut skewed = LocalSpace::new(Vector::new(0.0, 0.0, 0.0));
+        skewed.side = Vector::new(1.0, 0.5, 0.0);
+        assert!(!skewed.is_orthonormal(1.0e-3));
+    }
+
+    #[test]
+    fn heading_angle_2d() {
+        let mut space = LocalSpace::new(Vector2D::new(0.0, 0.0));
+        space.set_heading_angle(std::f32::consts::FRAC_PI_2);
+        assert!(space.forward.approx_eq(Vector2D::new(0.0, 1.0)));
+        assert!(space.side.approx_eq(Vector2D::new(1.0, 0.0)));
+        assert!((space.heading_angle() - std::f32::consts::FRAC_PI_2).abs() < 1.0e-5);
+        assert!(space.is_orthonormal(1.0e-5));
+    }
+
+    #[test]
+    fn yaw_pitch_roll() {
+        let mut space = LocalSpace::new(Vector::new(0.0, 0.0, 0.0));
+        // quarter yaw about +y turns forward (+z) towards +x
+        space.rotate(std::f32::consts::FRAC_PI_2, 0.0, 0.0);
+        assert!(space.forward.approx_eq(Vector::new(1.0, 0.0, 0.0)));
+        assert!(space.up.approx_eq(Vector::new(0.0, 1.0, 0.0)));
+
+        for _ in 0..1000 {
+            space.rotate(0.01, 0.02, 0.03);
+        }
+        assert!(space.is_orthonormal(1.0e-5));
+    }
+
+    #[test]
+    fn turn_towards_is_limited_and_shortest() {
+        let mut space = LocalSpace::new(Vector2D::new(0.0, 0.0));
+        space.set_heading_angle(0.0);
+        // target at 135 degrees, turns counterclockwise
+        let turned = space.turn_towards(Vector2D::new(-1.0, 1.0), 0.5, None);
+        assert_eq!(turned, 0.5);
+        assert!((space.heading_angle() - 0.5).abs() < 1.0e-5);
+        let turned = space.turn_towards(Vector2D::new(-1.0, 1.0), 10.0, None);
+        assert!((turned - (0.75 * std::f32::consts::PI - 0.5)).abs() < 1.0e-4);
+        assert!(space.forward.approx_eq(Vector2D::new(-1.0, 1.0).normalized()));
+
+        let mut space = LocalSpace::new(Vector::new(0.0, 0.0, 0.0));
+        space.turn_towards(Vector::new(0.0, 0.0, -1.0), 0.1, None);
+        assert!(space.forward.approx_eq(Vector::new(0.1_f32.sin(), 0.0, 0.1_f32.cos())));
+        assert!(space.is_orthonormal(1.0e-5));
+    }
+
+    #[test]
+    fn offset_between_frames() {
+        let space = rotated_frame();
+        let mut other = LocalSpace::new(Vector::new(1.0, 2.0, 3.0));
+        other.rotate(0.3, -0.2, 0.9);
+
+        let offset = space.localize_offset(&other);
+        assert!(offset.position.approx_eq(space.localize_position(other.position)));
+        assert!(offset.forward.approx_eq(space.localize_direction(other.forward)));
+        assert!(offset.is_orthonormal(1.0e-4));
+
+        let back = space.globalize_offset(&offset);
+        assert!(back.position.approx_eq_eps(other.position, 1.0e-4));
+        assert!(back.forward.approx_eq_eps(other.forward, 1.0e-4));
+        assert!(back.side.approx_eq_eps(other.side, 1.0e-4));
+        assert!(back.up.approx_eq_eps(other.up, 1.0e-4));
+
+        // a leader heading along +x with a follower 5 behind it facing the same way
+        let mut leader = LocalSpace::new(Vector2D::new(10.0, 0.0));
+        leader.set_heading_angle(0.0);
+        let mut follower = LocalSpace::new(Vector2D::new(5.0, 0.0));
+        follower.set_heading_angle(0.0);
+        let slot = leader.localize_offset(&follower);
+        assert!(slot.position.approx_eq(Vector2D::new(0.0, -5.0)));
+        assert!(slot.forward.approx_eq(Vector2D::FORWARD));
+    }
+}
@@ -1,132 +1,145 @@
-// A Demonstration of Seek Flee behavior based on work by Craig Reynolds 
-// These are 2D visualizations of 3D steering behaviors 
-
-mod vector;
-mod simple_vehicle;
-mod seeker;
-mod local_space;
+// Demonstrations of steering behaviors based on work by Craig Reynolds 
+// The steering code is dimension generic, the demos run it in 2D with Vector2D
 
 // ggez classes
-use ggez::graphics::DrawParam;
-use ggez::{event, graphics, Context, ContextBuilder, GameResult};
+use ggez::{event, graphics, Context, ContextBuilder, GameError, GameResult};
 use ggez::event::EventHandler;
-use ggez::glam::*;
+use ggez::glam::Vec2;
+use ggez::input::keyboard::{KeyCode, KeyInput};
+use ggez::input::mouse::MouseButton;
 
 // Defined classes
-use vector::Vector;
-use seeker::Seeker;
+use boids::math_mode::MathMode;
+use boids::random;
+use boids::vehicle_params::VehicleParams;
+use boids::integrator::INTEGRATOR_NAMES;
+use boids::integrator_harness;
+use boids::vector2d::Vector2D;
+
+// the scenes, see demos/mod.rs
+mod demos;
+use demos::{demo_from_name, Demo, DEMO_NAMES};
 
 const WIDTH: f32 = 560.0;
 const HEIGHT: f32 = 560.0;
 
-const SCALE: f32 = 15.0;
+// the simulation steps at a fixed rate no matter how often ggez calls update
+const UPDATES_PER_SECOND: u32 = 60;
+
+// settings picked on the command line, e.g. `cargo run -- --math approximate --seed 42`
+struct Options {
+    math_mode: MathMode,
+    seed: u64,
+    params: VehicleParams,
+    integrator: String,
+    // scene shown first, the number keys switch scenes
+    demo: String,
+    // print the integrator comparison and exit without opening a window
+    integrator_report: bool,
+}
 
-// seek is seeking one target, flee is fleeing from a different one
-// why are there two targets lmfao
+impl Options {
+    fn from_args() -> Result<Options, String> {
+        let mut options = Options {
+            math_mode: MathMode::default(),
+            seed: random::random_seed(),
+            params: VehicleParams::default(),
+            integrator: String::from("semi-implicit-euler"),
+            demo: String::from(DEMO_NAMES[0]),
+            integrator_report: false,
+        };
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--math" => {
+                    let name = args.next().ok_or("--math needs a value (exact or approximate)")?;
+                    options.math_mode = MathMode::from_name(&name)
+                        .ok_or(format!("unknown math mode '{}', expected exact or approximate", name))?;
+                }
+                "--seed" => {
+                    let seed = args.next().ok_or("--seed needs a value")?;
+                    options.seed = seed
+                        .parse()
+                        .map_err(|_| format!("seed '{}' is not an unsigned integer", seed))?;
+                }
+                "--preset" => {
+                    let name = args.next().ok_or("--preset needs a value (demo or opensteer)")?;
+                    options.params = VehicleParams::from_preset(&name)
+                        .ok_or(format!("unknown vehicle preset '{}', expected demo or opensteer", name))?;
+                }
+                "--integrator" => {
+                    let name = args.next().ok_or("--integrator needs a value")?;
+                    if !INTEGRATOR_NAMES.contains(&name.as_str()) {
+                        return Err(format!("unknown integrator '{}', expected one of {}", name, INTEGRATOR_NAMES.join(", ")));
+                    }
+                    options.integrator = name;
+                }
+                "--demo" => {
+                    let name = args.next().ok_or("--demo needs a value")?;
+                    if !DEMO_NAMES.contains(&name.as_str()) {
+                        return Err(format!("unknown demo '{}', expected one of {}", name, DEMO_NAMES.join(", ")));
+                    }
+                    options.demo = name;
+                }
+                "--integrator-report" => options.integrator_report = true,
+                _ => return Err(format!("unknown argument '{}'", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
 
 fn main() -> GameResult {
+    let options = Options::from_args().map_err(GameError::CustomError)?;
+    if options.integrator_report {
+        print!("{}", integrator_harness::report());
+        return Ok(());
+    }
+
     // create a build a context
-    let (mut ctx, event_loop) = ContextBuilder::new("SeekFleeDemo", "David Huang")
+    let (mut ctx, event_loop) = ContextBuilder::new("SteeringDemo", "David Huang")
         .window_mode(ggez::conf::WindowMode::default().dimensions(WIDTH, HEIGHT))
-        .window_setup(ggez::conf::WindowSetup::default().title("Seek Flee Demo"))
+        .window_setup(ggez::conf::WindowSetup::default().title("Steering Behavior Demos"))
         .build()
         .expect("Could not create context");
-    let mut seekflee = SeekFlee::new(&mut ctx);
-    seekflee.reset();
+    let app = App::new(&mut ctx, options);
 
     // run
-    event::run(ctx, event_loop, seekflee);
+    event::run(ctx, event_loop, app);
 }
 
-struct SeekFlee {
-    // This is the main state, and references the starting state 
-    target_position: Vector,
-    // position of seek vehicle
-    seek_vehicle: Seeker,
-    flee_vehicle: Seeker, 
-    frames_since_touch: u32,
-    draw_once: bool,
-    new_accel: Vector,
-    accel_up: Vector,
-    bank_up: Vector,
-    draw_steer: Vector,
-    steering: Vector,
+// the scene being shown and the options every scene is built from
+struct App {
+    options: Options,
+    demo_name: &'static str,
+    demo: Box<dyn Demo>,
 }
 
-impl SeekFlee {
-    pub fn new(mut _ctx: &mut Context) -> SeekFlee {
-        // calculating middle and assigning target position
-        let (mid_width, mid_height) = (WIDTH * 0.5, HEIGHT * 0.5);
-        let target_pos = Vector::new(mid_width, mid_height, 0.0);
-
-        // define a view center to make initial position relative to that
-        // let view_center = Vector::new(mid_width / (2.0 * SCALE), mid_height / (2.0 * SCALE), 0.0);
-
-        // define two seek vehicles
-        let mut seek = Seeker::new();
-        let mut flee = Seeker::new();
-        flee.seek = false;
-        // test
-
-        SeekFlee {
-            target_position: target_pos,
-            seek_vehicle: seek,
-            flee_vehicle: flee,
-            frames_since_touch: 0,
-            draw_once: true,
-            new_accel: Vector::new(0.0, 0.0, 0.0),
-            accel_up: Vector::new(0.0, 0.0, 0.0),
-            bank_up: Vector::new(0.0, 0.0, 0.0),
-            draw_steer: Vector::new(0.0, 0.0, 0.0), // this is used for drawing the steering force and velocity 
-            steering: Vector::new(0.0, 0.0, 0.0),
-        }
+impl App {
+    fn new(_ctx: &mut Context, options: Options) -> App {
+        let demo_name = DEMO_NAMES.iter().copied().find(|&name| name == options.demo).unwrap_or(DEMO_NAMES[0]);
+        let mut app = App {
+            demo: demo_from_name(demo_name, &options).expect("every listed demo has a name"),
+            demo_name,
+            options,
+        };
+        app.demo.reset();
+        app
     }
 
-    pub fn reset(&mut self) {
-        let mid_width = WIDTH * 0.5;
-        let mid_height = HEIGHT * 0.5;
-         // define a view center to make initial position relative to that
-        let view_center = Vector::new(mid_width,  mid_height, 0.0);
-        // initial position should be something random
-        // set unit random used to generate a random velocity and position
-        // initial position vector
-        self.seek_vehicle.vehicle.local_space.position.gen_random_vector();
-        // position scaled by 17.0
-        self.seek_vehicle.vehicle.local_space.position.set_scale(170.0, self.seek_vehicle.vehicle.local_space.position);
-        self.seek_vehicle.vehicle.local_space.position.set_sum(view_center, self.seek_vehicle.vehicle.local_space.position);
-        self.seek_vehicle.vehicle.local_space.position.z = 0.0;
-
-        // println!("initial_pos: {:?}", self.seek_vehicle.vehicle.local_space.position);
-
-        // velocity scaled by max Speed
-        // set target
-        self.seek_vehicle.vehicle.velocity.gen_random_vector();
-        self.seek_vehicle.vehicle.velocity.set_scale(self.seek_vehicle.vehicle.max_speed, self.seek_vehicle.vehicle.velocity);
-        self.seek_vehicle.vehicle.velocity.z = 0.0;
-        self.seek_vehicle.target = self.target_position;
-        self.seek_vehicle.touch = false;
-
-        // initialize flee vehicle with identical values
-        self.flee_vehicle.seek = false;
-        self.flee_vehicle.vehicle.local_space.position.set(self.seek_vehicle.vehicle.local_space.position);
-        self.flee_vehicle.vehicle.velocity.set(self.seek_vehicle.vehicle.velocity);
-        self.flee_vehicle.target = self.target_position;
-        self.flee_vehicle.touch = false;
+    // every switch builds the scene fresh, so it starts from the seed again
+    fn switch_to(&mut self, demo_name: &'static str) {
+        self.demo = demo_from_name(demo_name, &self.options).expect("every listed demo has a name");
+        self.demo_name = demo_name;
+        self.demo.reset();
     }
 }
 
-impl EventHandler for SeekFlee {
-    fn update(&mut self, _ctx: &mut Context) -> GameResult {
-        // update code
-        self.seek_vehicle.update(&mut self.new_accel, &mut self.accel_up, &mut self.bank_up);
-        self.flee_vehicle.update(&mut self.new_accel, &mut self.accel_up, &mut self.bank_up); 
-
-        //self.flee_vehicle.touch = self.seek_vehicle.touch;
-
-        self.frames_since_touch = if self.seek_vehicle.touch {self.frames_since_touch + 1} else {0};
-        if self.frames_since_touch > 15 {
-            self.reset();
-            self.frames_since_touch = 0;
+impl EventHandler for App {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        // fixed timestep accumulator, runs as many steps as the elapsed time calls for
+        while ctx.time.check_update_time(UPDATES_PER_SECOND) {
+            self.demo.step(1.0 / UPDATES_PER_SECOND as f32);
         }
         Ok(())
     }
@@ -135,11 +148,57 @@ impl EventHandler for SeekFlee {
         // draw background 
         let mut canvas = graphics::Canvas::from_frame(ctx, graphics::Color::from_rgb(230, 230, 153));
 
-        // moved draw functions to seeker.rs
-        self.seek_vehicle.draw(&mut self.draw_steer, ctx, &mut canvas, SCALE)?;
-        self.flee_vehicle.draw(&mut self.draw_steer, ctx, &mut canvas, SCALE)?;
+        self.demo.draw(ctx, &mut canvas)?;
+
+        // report how far off the length math has been during this run
+        let worst_error = self.demo.vehicles().iter().map(|vehicle| vehicle.math.worst_error()).fold(0.0, f32::max);
+        let report = graphics::Text::new(format!(
+            "demo: {} (keys 1-{} switch, R resets)\nseed: {}\nmath: {}  worst length error: {:.2}%\n{}",
+            self.demo_name,
+            DEMO_NAMES.len(),
+            self.options.seed,
+            self.options.math_mode.name(),
+            worst_error * 100.0,
+            self.demo.status()
+        ));
+        canvas.draw(&report, graphics::DrawParam::default().dest(Vec2::new(8.0, 8.0)).color(graphics::Color::BLACK));
 
         canvas.finish(ctx)?;
         Ok(())
     }
-}
\ No newline at end of file
+
+    fn key_down_event(&mut self, ctx: &mut Context, input: KeyInput, _repeated: bool) -> GameResult {
+        let Some(key) = input.keycode else {
+            return Ok(());
+        };
+        let number_keys = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5, KeyCode::Key6];
+        match key {
+            KeyCode::Escape => ctx.request_quit(),
+            KeyCode::R => self.demo.reset(),
+            _ => match number_keys.iter().position(|&number| number == key) {
+                Some(index) if index < DEMO_NAMES.len() => self.switch_to(DEMO_NAMES[index]),
+                _ => self.demo.key_pressed(key),
+            },
+        }
+        Ok(())
+    }
+
+    fn mouse_button_down_event(&mut self, _ctx: &mut Context, button: MouseButton, x: f32, y: f32) -> GameResult {
+        if button == MouseButton::Left {
+            self.demo.mouse_pressed(Vector2D::new(x, y));
+        }
+        Ok(())
+    }
+
+    fn mouse_motion_event(&mut self, _ctx: &mut Context, x: f32, y: f32, _dx: f32, _dy: f32) -> GameResult {
+        self.demo.mouse_moved(Vector2D::new(x, y));
+        Ok(())
+    }
+
+    fn mouse_button_up_event(&mut self, _ctx: &mut Context, button: MouseButton, _x: f32, _y: f32) -> GameResult {
+        if button == MouseButton::Left {
+            self.demo.mouse_released();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+// math_mode.rs
+// picks between exact and fast approximate length math for a simulation
+use std::cell::Cell;
+
+use crate::vector_space::VectorSpace;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum MathMode {
+    // sqrt based lengths, a vehicle's top speed matches max_speed
+    Exact,
+    // the 0.9375/0.375 estimate from the original applets, cheaper but off by several percent.
+    // the default so runs behave as they always have
+    #[default]
+    Approximate,
+}
+
+impl MathMode {
+    // "exact", or "approximate" and its short form "approx", as given to --math
+    pub fn from_name(name: &str) -> Option<MathMode> {
+        match name {
+            "exact" => Some(MathMode::Exact),
+            "approximate" | "approx" => Some(MathMode::Approximate),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MathMode::Exact => "exact",
+            MathMode::Approximate => "approximate",
+        }
+    }
+
+    pub fn length<V: VectorSpace>(self, vec: V) -> f32 {
+        match self {
+            MathMode::Exact => vec.length(),
+            MathMode::Approximate => vec.approximate_length(),
+        }
+    }
+}
+
+// length math used by a vehicle, every truncate/length call in the steering code goes through here
+// so the worst relative error against the exact length can be reported after a run
+#[derive(Debug, Default)]
+pub struct VectorMath {
+    pub mode: MathMode,
+    worst_error: Cell<f32>,
+}
+
+impl VectorMath {
+    pub fn new(mode: MathMode) -> Self {
+        VectorMath {
+            mode,
+            worst_error: Cell::new(0.0),
+        }
+    }
+
+    pub fn length<V: VectorSpace>(&self, vec: V) -> f32 {
+        let length = self.mode.length(vec);
+        self.record_error(vec, length);
+        length
+    }
+
+    pub fn distance<V: VectorSpace>(&self, vec: V, vec2: V) -> f32 {
+        self.length(vec - vec2)
+    }
+
+    // same direction but no longer than max_length
+    pub fn truncate<V: VectorSpace>(&self, vec: V, max_length: f32) -> V {
+        let length = self.length(vec);
+        if length > max_length {
+            vec * (max_length / length)
+        } else {
+            vec
+        }
+    }
+
+    // worst relative error seen so far, always 0.0 in exact mode
+    pub fn worst_error(&self) -> f32 {
+        self.worst_error.get()
+    }
+
+    pub fn reset_worst_error(&self) {
+        self.worst_error.set(0.0);
+    }
+
+    fn record_error<V: VectorSpace>(&self, vec: V, length: f32) {
+        if self.mode == MathMode::Exact {
+            return;
+        }
+
+        let exact = vec.length();
+        if exact > 0.0 {
+            let error = ((length - exact) / exact).abs();
+            if error > self.worst_error.get() {
+                self.worst_error.set(error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::{Vector, EPSILON};
+    use crate::vector2d::Vector2D;
+
+    #[test]
+    fn exact_math_matches_sqrt_and_reports_no_error() {
+        let math = VectorMath::new(MathMode::Exact);
+        assert_eq!(math.length(Vector::new(3.0, 4.0, 0.0)), 5.0);
+        assert_eq!(math.distance(Vector2D::new(4.0, 6.0), Vector2D::new(1.0, 2.0)), 5.0);
+        assert!(math.truncate(Vector2D::new(3.0, 4.0), 2.5).approx_eq(Vector2D::new(1.5, 2.0)));
+        assert_eq!(math.truncate(Vector2D::new(3.0, 4.0), 10.0), Vector2D::new(3.0, 4.0));
+        assert_eq!(math.worst_error(), 0.0);
+    }
+
+    #[test]
+    fn approximate_math_tracks_the_worst_error() {
+        let math = VectorMath::new(MathMode::Approximate);
+        assert_eq!(MathMode::default(), MathMode::Approximate);
+
+        // 4 * 0.9375 + 3 * 0.375, 2.5% short of 5
+        assert!((math.length(Vector::new(3.0, 4.0, 0.0)) - 4.875).abs() < EPSILON);
+        assert!((math.worst_error() - 0.025).abs() < EPSILON);
+        assert!((math.distance(Vector2D::new(4.0, 6.0), Vector2D::new(1.0, 2.0)) - 4.875).abs() < EPSILON);
+
+        // along an axis the estimate is 6.25% short, which becomes the worst
+        let truncated = math.truncate(Vector2D::new(0.0, 10.0), 3.75);
+        assert!(truncated.approx_eq(Vector2D::new(0.0, 4.0)));
+        assert!((math.worst_error() - 0.0625).abs() < EPSILON);
+
+        // a smaller error later does not lower it, a reset does
+        math.length(Vector::new(3.0, 4.0, 0.0));
+        assert!((math.worst_error() - 0.0625).abs() < EPSILON);
+        math.reset_worst_error();
+        assert_eq!(math.worst_error(), 0.0);
+        math.length(Vector::ZERO);
+        assert_eq!(math.worst_error(), 0.0);
+    }
+}
@@ -0,0 +1,76 @@
+// obstacle.rs
+// round obstacles, circles in 2D and spheres in 3D
+use crate::local_space::LocalSpace;
+use crate::vector_space::VectorSpace;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SphereObstacle<V: VectorSpace> {
+    pub center: V,
+    pub radius: f32,
+}
+
+// where a path first runs into an obstacle
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PathIntersection<V: VectorSpace> {
+    // along forward from the frame's position, negative when the frame already overlaps it
+    pub distance: f32,
+    // the obstacle's center off the path in the frame's local coordinates, zero when dead ahead
+    pub lateral_offset: V,
+}
+
+impl<V: VectorSpace> SphereObstacle<V> {
+    pub fn new(center: V, radius: f32) -> Self {
+        SphereObstacle { center, radius }
+    }
+
+    pub fn contains(&self, point: V) -> bool {
+        point.distance(self.center) <= self.radius
+    }
+
+    // sweeps a body of body_radius along space.forward for length, None when it stays clear
+    pub fn path_intersection(&self, space: &LocalSpace<V>, body_radius: f32, length: f32) -> Option<PathIntersection<V>> {
+        let local_center = space.localize_position(self.center);
+        let ahead = local_center.dot(V::FORWARD);
+        let lateral_offset = local_center - V::FORWARD * ahead;
+        let combined_radius = self.radius + body_radius;
+
+        let lateral_squared = lateral_offset.length_squared();
+        if lateral_squared >= combined_radius * combined_radius {
+            return None;
+        }
+        // the swept body enters the obstacle this far ahead of the center's foot on the path
+        let distance = ahead - (combined_radius * combined_radius - lateral_squared).sqrt();
+        // behind us entirely, or further than the sweep reaches
+        if ahead + combined_radius < 0.0 || distance > length {
+            return None;
+        }
+        Some(PathIntersection { distance, lateral_offset })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::Vector;
+    use crate::vector2d::Vector2D;
+
+    #[test]
+    fn sweep_finds_obstacles_on_the_path() {
+        // 2D frame at the origin looking along +y
+        let space = LocalSpace::new(Vector2D::ZERO);
+        let ahead = SphereObstacle::new(Vector2D::new(3.0, 50.0), 5.0);
+        let hit = ahead.path_intersection(&space, 1.0, 100.0).expect("in the path");
+        assert!((hit.distance - (50.0 - 27.0_f32.sqrt())).abs() < 1.0e-4);
+        assert!(hit.lateral_offset.approx_eq(Vector2D::new(3.0, 0.0)));
+
+        assert_eq!(ahead.path_intersection(&space, 1.0, 40.0), None);
+        assert_eq!(SphereObstacle::new(Vector2D::new(7.0, 50.0), 5.0).path_intersection(&space, 1.0, 100.0), None);
+        assert_eq!(SphereObstacle::new(Vector2D::new(0.0, -20.0), 5.0).path_intersection(&space, 1.0, 100.0), None);
+
+        let space = LocalSpace::new(Vector::ZERO);
+        let sphere = SphereObstacle::new(Vector::new(0.0, -2.0, 30.0), 4.0);
+        let hit = sphere.path_intersection(&space, 0.0, 100.0).expect("in the path");
+        assert!(hit.lateral_offset.approx_eq(Vector::new(0.0, -2.0, 0.0)));
+        assert!(sphere.contains(Vector::new(0.0, 0.0, 28.0)));
+    }
+}
@@ -1,89 +1,282 @@
 use crate::vector::Vector;
+use crate::vector_space::VectorSpace;
 use crate::local_space::LocalSpace;
+use crate::math_mode::{MathMode, VectorMath};
+use crate::vehicle_params::{TurnLimits, VehicleParams};
+use crate::integrator::{BoxedIntegrator, MotionState, SemiImplicitEuler};
+use crate::trail::Trail;
 
-// DEFAULT VALUES USED
-// mass is 1.0F
-// max speed is 1.0F
-// max force is 0.04F
-// acceleration damping value of 0.7
-const ACCELERATION_DAMPING: f32 = 0.99;
-const GLOBAL_UP: Vector = Vector { x: 0.0, y: 0.1, z: 0.0};
-// a lot of static variables from the previous implemetation 
-/*
-accelUp
-bankUp
-newAccel
-*/
-
-// implementation works fine, values are 
-
-pub struct SimpleVehicle {
-    pub local_space: LocalSpace, // extends LocalSpace in old code, but this is how we will include inheritance
-    pub mass: f32,
-    pub max_speed: f32,
-    pub max_force: f32,
-    pub velocity: Vector,
-    pub all_forces: Vector,
-
-    pub acceleration: Vector,
+// mass, max speed, max force and damping live in VehicleParams, in per-second units
+
+// the original applets stepped once per 60 Hz frame with no time term, their per-frame
+// constants are converted to per-second units with this
+pub const REFERENCE_TIME_STEP: f32 = 1.0 / 60.0;
+// global up is V::UP scaled by this, zero in 2D
+const GLOBAL_UP_SCALE: f32 = 0.1;
+// the previous implementation kept accelUp, bankUp and newAccel as statics, they are
+// locals in update so every vehicle can be stepped on its own
+
+// share of the new value to blend into a running average for a rate per second. exponential
+// so that any number of steps covering the same time blend in the same share
+fn blend(rate: f32, dt: f32) -> f32 {
+    1.0 - (-rate * dt).exp()
+}
+
+// where two vehicles come closest if both keep their current velocity, see SimpleVehicle::closest_approach
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ClosestApproach<V: VectorSpace> {
+    // seconds from now, zero when the vehicles are already moving apart
+    pub time: f32,
+    pub distance: f32,
+    pub position: V,
+    pub other_position: V,
 }
 
-impl SimpleVehicle {
-    pub fn new(position: Vector) -> Self {
+// generic over the vector type, SimpleVehicle<Vector2D> for the 2D demos, plain SimpleVehicle is 3D.
+// speeds are world units per second and forces world units per second squared
+pub struct SimpleVehicle<V: VectorSpace = Vector> {
+    pub local_space: LocalSpace<V>, // extends LocalSpace in old code, but this is how we will include inheritance
+    // mass, max speed, max force and so on, can be changed per vehicle at any time
+    pub params: VehicleParams,
+    pub velocity: V,
+    pub all_forces: V,
+
+    pub acceleration: V,
+    // length math picked by the simulation
+    pub math: VectorMath,
+    // how velocity and position are advanced, semi-implicit Euler unless changed
+    pub integrator: BoxedIntegrator<V>,
+    // how fast forward is turning in radians per second, positive towards side. in 3D turns
+    // out of the forward/side plane take the sign of their side component.
+    // only used when params.turning is set
+    pub angular_velocity: f32,
+
+    // path annotation, updated every step with the blend rates from params.
+    // curvature is 1 / turning radius, positive when turning towards side
+    pub curvature: f32,
+    pub smoothed_curvature: f32,
+    pub smoothed_acceleration: V,
+    pub smoothed_position: V,
+    last_forward: V,
+    last_position: V,
+    // where the vehicle has been, replace it to change the capacity or sample interval
+    pub trail: Trail<V>,
+}
+
+impl<V: VectorSpace> SimpleVehicle<V> {
+    // uses the demo preset, see VehicleParams
+    pub fn new(position: V, math_mode: MathMode) -> Self {
+        SimpleVehicle::with_params(position, math_mode, VehicleParams::default())
+    }
+
+    pub fn with_params(position: V, math_mode: MathMode, params: VehicleParams) -> Self {
         SimpleVehicle {
             local_space: LocalSpace::new(position),
-            mass: 1.0,
-            // original values are 0.08 and 0.06
-            max_speed: 0.64,
-            max_force: 0.48,
-            velocity: Vector::new(0.0, 0.0, 0.0),
-            all_forces: Vector::new(0.0, 0.0, 0.0),
-            acceleration: Vector::new(0.0, 0.0, 0.0),
+            params,
+            velocity: V::ZERO,
+            all_forces: V::ZERO,
+            acceleration: V::ZERO,
+            math: VectorMath::new(math_mode),
+            integrator: Box::new(SemiImplicitEuler),
+            angular_velocity: 0.0,
+            curvature: 0.0,
+            smoothed_curvature: 0.0,
+            smoothed_acceleration: V::ZERO,
+            smoothed_position: position,
+            last_forward: V::FORWARD,
+            last_position: position,
+            trail: Trail::default(),
         }
     }
 
-    pub fn apply_global_force(&mut self, force: Vector){
-        self.all_forces.set_sum(self.all_forces, force);
+    // restarts the smoothed values and the trail from the current state, call after moving the vehicle by hand
+    pub fn reset_smoothing(&mut self) {
+        self.trail.clear();
+        self.curvature = 0.0;
+        self.smoothed_curvature = 0.0;
+        self.smoothed_acceleration = V::ZERO;
+        self.smoothed_position = self.local_space.position;
+        self.last_forward = self.local_space.forward;
+        self.last_position = self.local_space.position;
     }
 
-    pub fn update(&mut self, new_accel: &mut Vector, accel_up: &mut Vector, bank_up: &mut Vector){
-        // truncate net forces using max forces
-        self.all_forces.set_approximate_truncate(self.max_force);
+    pub fn apply_global_force(&mut self, force: V){
+        self.all_forces += force;
+    }
 
-        if self.mass == 1.0 {
-            new_accel.set(self.all_forces);
-        } else {
-            new_accel.set_scale(1.0 / self.mass, self.all_forces);
+    // force along forward that takes the speed to target_speed within a reference step,
+    // at most max_force either way. scaled by mass since update divides forces by it
+    pub fn steer_for_target_speed(&self, target_speed: f32) -> V {
+        let speed_error = target_speed - self.math.length(self.velocity);
+        let max_force = self.params.max_force;
+        self.local_space.forward * (self.params.mass * speed_error / REFERENCE_TIME_STEP).clamp(-max_force, max_force)
+    }
+
+    pub fn apply_target_speed(&mut self, target_speed: f32) {
+        let force = self.steer_for_target_speed(target_speed);
+        self.apply_global_force(force);
+    }
+
+    // slows the vehicle along -forward, rate is the share of the current speed to take off per
+    // second whatever the mass, as long as that stays under max_force
+    pub fn apply_braking_force(&mut self, rate: f32) {
+        let braking = (self.params.mass * self.math.length(self.velocity) * rate).min(self.params.max_force);
+        self.apply_global_force(-self.local_space.forward * braking);
+    }
+
+    // advances the vehicle by dt seconds
+    pub fn update(&mut self, dt: f32){
+        if let Some(cruise_speed) = self.params.cruise_speed {
+            self.apply_target_speed(cruise_speed);
         }
-        self.all_forces.set_to_zero();
 
-        // acceleration should be Interpolated using accelDamping, newAccel, and acceleration
-        self.acceleration.set_interpolation(ACCELERATION_DAMPING, new_accel, self.acceleration);
-        // add acceleration to velocity 
-        self.velocity.set_sum(self.velocity, self.acceleration); // should be acceleration, but only works with new accel?
-        // truncate velocity 
-        self.velocity.set_approximate_truncate(self.max_speed);
-        // add velocity to position 
-        self.local_space.position.set_sum(self.local_space.position, self.velocity);
+        // truncate net forces using max forces
+        self.all_forces = self.math.truncate(self.all_forces, self.params.max_force);
+
+        let new_accel = self.all_forces / self.params.mass;
+        self.all_forces = V::ZERO;
+
+        // acceleration should be Interpolated using accelDamping, newAccel, and acceleration,
+        // the damping compounds once per reference step so it decays the same at any frame rate
+        let damping = self.params.acceleration_damping.powf(dt / REFERENCE_TIME_STEP);
+        self.acceleration = new_accel.lerp(self.acceleration, damping);
 
         // banking, but not sure how this works in 2D space
-        accel_up.set_scale(0.5, self.acceleration);
-        bank_up.set_sum(self.local_space.up, *accel_up);
-        bank_up.set_sum(*bank_up, GLOBAL_UP); 
-        bank_up.set_normalize(); 
+        // the banking constants were tuned for per-frame acceleration
+        let accel_up = self.acceleration * (0.5 * REFERENCE_TIME_STEP * REFERENCE_TIME_STEP);
+        let bank_up = (self.local_space.up + accel_up + V::UP * GLOBAL_UP_SCALE).normalized();
+
+        // with turn limits forward turns first, towards where the acceleration would take the
+        // velocity, and the velocity is kept along it before it moves the vehicle
+        if let Some(limits) = self.params.turning {
+            let desired_velocity = self.velocity + self.acceleration * dt;
+            self.turn_towards_velocity(limits, desired_velocity, bank_up, dt);
+        }
 
-        // println!("Bank Up: x: {} y: {} z: {}", bank_up.x, bank_up.y, bank_up.z);
-        // println!("Accel Up: x: {} y: {} z: {}", accel_up.x, accel_up.y, accel_up.z);
-        // println!("New Accel: x: {} y: {} z: {}", new_accel.x, new_accel.y, new_accel.z);
+        // integrate acceleration into velocity and position, truncating velocity before it moves
+        // the vehicle. the steering was worked out for the state at the start of the step, so the
+        // acceleration is held constant over it
+        let acceleration = self.acceleration;
+        let (math, max_speed) = (&self.math, self.params.max_speed);
+        let forward = self.params.turning.map(|_| self.local_space.forward);
+        let constrain_velocity = |velocity: V| {
+            let velocity = match forward {
+                Some(forward) => forward * velocity.dot(forward).max(0.0),
+                None => velocity,
+            };
+            math.truncate(velocity, max_speed)
+        };
+        let state = MotionState {
+            position: self.local_space.position,
+            velocity: self.velocity,
+        };
+        let state = self.integrator.step(state, dt, acceleration, &constrain_velocity);
+        self.velocity = state.velocity;
+        self.local_space.position = state.position;
 
         // something to do with local space, but again unsure how this works tbh
-        let speed: f32 = self.velocity.magnitude();
-        if speed > 0.0 {  
-            self.local_space.forward.set_scale(1.0 / speed, self.velocity);
-            self.local_space.side.set_cross(self.local_space.forward, *bank_up);
-            self.local_space.up.set_cross(self.local_space.side, self.local_space.forward);
+        let speed: f32 = self.velocity.length();
+        if self.params.turning.is_none() && speed > 0.0 {
+            self.local_space.regenerate_basis(self.velocity / speed, Some(bank_up));
         }
 
+        self.annotate_path(new_accel, dt);
+    }
+
+    // turns forward towards desired_velocity within the turn limits, slowing the turn in time
+    // to stop on it. the angular velocity is signed, so a target that swaps sides has to brake
+    // the turn before it can reverse it
+    fn turn_towards_velocity(&mut self, limits: TurnLimits, desired_velocity: V, bank_up: V, dt: f32) {
+        let max_change = limits.max_angular_acceleration * dt;
+        let desired = desired_velocity.normalized();
+        if desired == V::ZERO {
+            self.angular_velocity -= self.angular_velocity.clamp(-max_change, max_change);
+            return;
+        }
+
+        // the plane of the turn, straight ahead or behind keeps turning the way it already is
+        let forward = self.local_space.forward;
+        let across = desired.perpendicular_component(forward).normalized();
+        let across = if across == V::ZERO {
+            if self.angular_velocity < 0.0 { -self.local_space.side } else { self.local_space.side }
+        } else {
+            across
+        };
+        // +1 when turning towards across is turning towards side
+        let sign = if across.dot(self.local_space.side) < 0.0 { -1.0 } else { 1.0 };
+
+        let angle = forward.dot(desired).clamp(-1.0, 1.0).acos();
+        let target_rate = (angle / dt)
+            .min(limits.max_turn_rate)
+            .min((2.0 * limits.max_angular_acceleration * angle).sqrt())
+            * sign;
+        self.angular_velocity = target_rate.clamp(self.angular_velocity - max_change, self.angular_velocity + max_change);
+
+        let (sin, cos) = (self.angular_velocity * sign * dt).sin_cos();
+        self.local_space.regenerate_basis(forward * cos + across * sin, Some(bank_up));
+    }
+
+    // updates the smoothed values and the trail after the vehicle moved, for models that move it their own way
+    pub(crate) fn annotate_path(&mut self, new_accel: V, dt: f32) {
+        self.smoothed_acceleration = self.smoothed_acceleration.lerp(new_accel, blend(self.params.acceleration_smoothing, dt));
+        self.measure_path_curvature(dt);
+        self.smoothed_position = self.smoothed_position.lerp(self.local_space.position, blend(self.params.position_smoothing, dt));
+        self.trail.record(self.local_space.position, dt);
+    }
+
+    // linear prediction, where the vehicle will be t seconds from now at its current velocity
+    pub fn predict_future_position(&self, t: f32) -> V {
+        self.local_space.position + self.velocity * t
+    }
+
+    // time constant of acceleration_damping, the seconds a change of force takes to mostly show
+    // up in the acceleration. zero without damping and infinite at a damping of 1
+    pub fn response_time(&self) -> f32 {
+        REFERENCE_TIME_STEP / (1.0 / self.params.acceleration_damping).ln()
+    }
+
+    // velocity the damped acceleration will still add if no more force is applied
+    pub fn pending_velocity(&self) -> V {
+        let response_time = self.response_time();
+        if response_time.is_finite() { self.acceleration * response_time } else { V::ZERO }
+    }
+
+    // solves for the time the relative position is shortest, as OpenSteer's
+    // computeNearestApproachPositions but in seconds
+    pub fn closest_approach(&self, other: &SimpleVehicle<V>) -> ClosestApproach<V> {
+        let relative_velocity = other.velocity - self.velocity;
+        let relative_position = other.local_space.position - self.local_space.position;
+        let relative_speed_squared = relative_velocity.length_squared();
+
+        // same velocity, the distance never changes
+        let time = if relative_speed_squared > 0.0 {
+            (-relative_position.dot(relative_velocity) / relative_speed_squared).max(0.0)
+        } else {
+            0.0
+        };
+
+        let position = self.predict_future_position(time);
+        let other_position = other.predict_future_position(time);
+        ClosestApproach {
+            time,
+            distance: position.distance(other_position),
+            position,
+            other_position,
+        }
+    }
+
+    // change in heading per distance travelled since the last step, as in OpenSteer
+    fn measure_path_curvature(&mut self, dt: f32) {
+        let distance = self.local_space.position.distance(self.last_position);
+        if dt > 0.0 && distance > 0.0 {
+            let heading_change = (self.local_space.forward - self.last_forward) / distance;
+            let lateral = heading_change.perpendicular_component(self.local_space.forward);
+            let sign = if lateral.dot(self.local_space.side) < 0.0 { -1.0 } else { 1.0 };
+            self.curvature = lateral.length() * sign;
+            self.smoothed_curvature += (self.curvature - self.smoothed_curvature) * blend(self.params.curvature_smoothing, dt);
+        }
+        self.last_forward = self.local_space.forward;
+        self.last_position = self.local_space.position;
     }
 
     // Deprecated in favor of the update function above
@@ -95,3 +288,282 @@ impl SimpleVehicle {
     //     self.position = self.position + self.velocity;
     // }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrator::{integrator_from_name, INTEGRATOR_NAMES};
+    use crate::vector::EPSILON;
+    use crate::vector2d::Vector2D;
+
+    #[test]
+    fn curvature_of_circular_path() {
+        let radius = 100.0;
+        let speed = 30.0;
+        let params = VehicleParams::builder().acceleration_damping(0.0).build();
+        let mut vehicle = SimpleVehicle::with_params(Vector2D::new(radius, 0.0), MathMode::Exact, params);
+        vehicle.velocity = Vector2D::new(0.0, speed);
+        vehicle.reset_smoothing();
+
+        for _ in 0..120 {
+            let center_pull = -vehicle.local_space.position.normalized() * (speed * speed / radius);
+            vehicle.apply_global_force(center_pull);
+            vehicle.update(REFERENCE_TIME_STEP);
+        }
+
+        // counterclockwise, so the turn is away from side
+        assert!((vehicle.curvature + 1.0 / radius).abs() < 1.0e-3, "curvature {}", vehicle.curvature);
+        assert!((vehicle.smoothed_curvature + 1.0 / radius).abs() < 1.0e-3, "smoothed {}", vehicle.smoothed_curvature);
+    }
+
+    #[test]
+    fn smoothed_acceleration_is_independent_of_time_step() {
+        // a steady push, so the raw acceleration is the same every step
+        let force = Vector2D::new(12.0, 0.0);
+        let smoothed_after = |dt: f32, seconds: f32| {
+            let params = VehicleParams::builder().acceleration_damping(0.0).max_speed(1000.0).build();
+            let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+            for _ in 0..(seconds / dt).round() as usize {
+                vehicle.apply_global_force(force);
+                vehicle.update(dt);
+            }
+            vehicle.smoothed_acceleration
+        };
+
+        let rate = VehicleParams::demo().acceleration_smoothing;
+        let expected = force * (1.0 - (-rate * 0.2).exp());
+        for dt in [1.0 / 30.0, 1.0 / 120.0] {
+            let smoothed = smoothed_after(dt, 0.2);
+            assert!(smoothed.distance(expected) < 1.0e-3, "at {} smoothed {:?}, expected {:?}", dt, smoothed, expected);
+        }
+        assert!(smoothed_after(1.0 / 60.0, 2.0).distance(force) < 1.0e-3);
+    }
+
+    #[test]
+    fn smoothed_position_is_independent_of_time_step() {
+        // a vehicle at rest, moved by hand without resetting the smoothing
+        let moved_to = Vector2D::new(50.0, -20.0);
+        let rate = 2.0;
+        let smoothed_after = |dt: f32, seconds: f32| {
+            let params = VehicleParams::builder().position_smoothing(rate).build();
+            let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+            vehicle.local_space.position = moved_to;
+            for _ in 0..(seconds / dt).round() as usize {
+                vehicle.update(dt);
+            }
+            vehicle.smoothed_position
+        };
+
+        let expected = moved_to * (1.0 - (-rate * 0.5).exp());
+        for dt in [1.0 / 30.0, 1.0 / 120.0] {
+            let smoothed = smoothed_after(dt, 0.5);
+            assert!(smoothed.distance(expected) < 1.0e-3, "at {} smoothed {:?}, expected {:?}", dt, smoothed, expected);
+        }
+        assert!(smoothed_after(1.0 / 60.0, 10.0).distance(moved_to) < 1.0e-3);
+    }
+
+    #[test]
+    fn damping_gives_the_same_path_at_any_time_step() {
+        // seeks a point from rest for two seconds with the preset's 0.99 damping
+        let end = |dt: f32| {
+            let mut vehicle = SimpleVehicle::new(Vector2D::ZERO, MathMode::Exact);
+            let target = Vector2D::new(300.0, 150.0);
+            let steps = (2.0 / dt).round() as usize;
+            for _ in 0..steps {
+                let desired = (target - vehicle.local_space.position).normalized() * vehicle.params.max_speed;
+                vehicle.apply_global_force((desired - vehicle.velocity) / REFERENCE_TIME_STEP);
+                vehicle.update(dt);
+            }
+            vehicle.local_space.position
+        };
+        // what is left is the integration error, about a coarse step's travel. damping
+        // compounded per step instead of per reference step puts them 4 apart
+        let (coarse, fine) = (end(1.0 / 30.0), end(1.0 / 120.0));
+        assert!(coarse.distance(fine) < 1.5, "1/30 ended at {:?}, 1/120 at {:?}", coarse, fine);
+        assert!(fine.length() > 20.0, "barely moved to {:?}", fine);
+    }
+
+    #[test]
+    fn cruise_speed_is_held_and_braking_slows_down() {
+        let params = VehicleParams::builder().acceleration_damping(0.0).cruise_speed(Some(20.0)).build();
+        let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+        vehicle.velocity = Vector2D::new(0.0, 1.0);
+        for _ in 0..600 {
+            vehicle.update(REFERENCE_TIME_STEP);
+        }
+        assert!((vehicle.velocity.length() - 20.0).abs() < 0.1, "speed {}", vehicle.velocity.length());
+
+        vehicle.params.cruise_speed = None;
+        for _ in 0..600 {
+            vehicle.apply_braking_force(2.0);
+            vehicle.update(REFERENCE_TIME_STEP);
+        }
+        assert!(vehicle.velocity.length() < 1.0, "speed {}", vehicle.velocity.length());
+        assert!(vehicle.velocity.dot(Vector2D::new(0.0, 1.0)) >= 0.0);
+    }
+
+    #[test]
+    fn target_speed_and_braking_account_for_mass() {
+        let dt = REFERENCE_TIME_STEP;
+        for mass in [1.0, 4.0] {
+            let params = VehicleParams::builder().mass(mass).acceleration_damping(0.0).build();
+            let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+            vehicle.velocity = Vector2D::new(0.0, 10.0);
+
+            // well within max_force, so one reference step lands on the target speed
+            vehicle.apply_target_speed(12.0);
+            vehicle.update(dt);
+            assert!((vehicle.velocity.length() - 12.0).abs() < 1.0e-3, "mass {} reached {}", mass, vehicle.velocity.length());
+
+            // braking at 3 per second takes off 3 * dt of the speed each step
+            vehicle.apply_braking_force(3.0);
+            vehicle.update(dt);
+            let expected = 12.0 * (1.0 - 3.0 * dt);
+            assert!((vehicle.velocity.length() - expected).abs() < 1.0e-3, "mass {} braked to {}", mass, vehicle.velocity.length());
+        }
+    }
+
+    #[test]
+    fn turn_limits_bound_heading_changes() {
+        let limits = TurnLimits { max_turn_rate: 3.0, max_angular_acceleration: 6.0 };
+        let params = VehicleParams::builder().turning(Some(limits)).build();
+        let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+        vehicle.local_space.set_heading_angle(0.0);
+        vehicle.velocity = Vector2D::new(vehicle.params.max_speed, 0.0);
+
+        // push straight back, the vehicle has to turn around rather than reverse
+        let dt = REFERENCE_TIME_STEP;
+        let max_change = limits.max_angular_acceleration * dt + 1.0e-4;
+        for _ in 0..300 {
+            let (before, angular_velocity) = (vehicle.local_space.forward, vehicle.angular_velocity);
+            let position = vehicle.local_space.position;
+            vehicle.apply_global_force(Vector2D::new(-vehicle.params.max_force, 0.0));
+            vehicle.update(dt);
+
+            let turned = before.dot(vehicle.local_space.forward).clamp(-1.0, 1.0).acos();
+            assert!(turned <= limits.max_turn_rate * dt + 1.0e-4);
+            assert!((vehicle.angular_velocity - angular_velocity).abs() <= max_change);
+            assert!(vehicle.angular_velocity.abs() <= limits.max_turn_rate + 1.0e-4);
+            assert!(vehicle.velocity.dot(vehicle.local_space.side).abs() < 1.0e-3);
+
+            // the step moved the vehicle along its new forward, never sideways or backwards
+            let moved = vehicle.local_space.position - position;
+            assert!(moved.dot(vehicle.local_space.side).abs() < 1.0e-4);
+            assert!(moved.dot(vehicle.local_space.forward) >= 0.0);
+        }
+        assert!(vehicle.local_space.forward.approx_eq_eps(Vector2D::new(-1.0, 0.0), 1.0e-3));
+    }
+
+    #[test]
+    fn turn_reversal_brakes_before_turning_back() {
+        let limits = TurnLimits { max_turn_rate: 3.0, max_angular_acceleration: 6.0 };
+        let params = VehicleParams::builder().turning(Some(limits)).build();
+        let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+        vehicle.local_space.set_heading_angle(0.0);
+        vehicle.velocity = Vector2D::new(vehicle.params.max_speed, 0.0);
+        // at full rate towards side (-y when heading +x), the target is now off the other way
+        vehicle.angular_velocity = limits.max_turn_rate;
+
+        let dt = REFERENCE_TIME_STEP;
+        let mut rates = vec![vehicle.angular_velocity];
+        for _ in 0..120 {
+            let heading = vehicle.local_space.heading_angle();
+            vehicle.apply_global_force(Vector2D::new(0.0, vehicle.params.max_force));
+            vehicle.update(dt);
+            rates.push(vehicle.angular_velocity);
+
+            // side is clockwise of forward, so turning towards it lowers the heading angle
+            let turned = Vector2D::new(1.0, 0.0).rotated(heading - vehicle.local_space.heading_angle());
+            let turned = turned.y.atan2(turned.x);
+            assert!((turned - vehicle.angular_velocity * dt).abs() < 1.0e-4, "turned {} at {}", turned, vehicle.angular_velocity);
+        }
+        for pair in rates.windows(2) {
+            assert!((pair[1] - pair[0]).abs() <= limits.max_angular_acceleration * dt + 1.0e-4, "{:?}", pair);
+        }
+        // it did reverse the turn and come round in the end
+        assert!(rates.iter().any(|rate| *rate < 0.0));
+        assert!(vehicle.local_space.forward.y > 0.0);
+    }
+
+    // seeks a target far ahead with max_force along the velocity from top speed, returning the positions
+    fn full_force_seek(integrator_name: &str, steps: usize) -> Vec<Vector2D> {
+        let params = VehicleParams::builder().acceleration_damping(0.0).build();
+        let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+        vehicle.integrator = integrator_from_name(integrator_name).expect("listed integrators have names");
+        vehicle.velocity = Vector2D::new(params.max_speed, 0.0);
+        let target = Vector2D::new(1.0e6, 1.0e5);
+        (0..steps)
+            .map(|_| {
+                let desired = (target - vehicle.local_space.position).normalized() * vehicle.params.max_speed;
+                vehicle.apply_global_force((desired - vehicle.velocity).normalized() * vehicle.params.max_force);
+                vehicle.update(REFERENCE_TIME_STEP);
+                vehicle.local_space.position
+            })
+            .collect()
+    }
+
+    #[test]
+    fn no_integrator_moves_faster_than_max_speed() {
+        let max_step = VehicleParams::default().max_speed * REFERENCE_TIME_STEP;
+        for name in INTEGRATOR_NAMES {
+            let positions = full_force_seek(name, 120);
+            for pair in positions.windows(2) {
+                let moved = pair[0].distance(pair[1]);
+                assert!(moved <= max_step * (1.0 + 1.0e-5), "{} moved {} in a step, max {}", name, moved, max_step);
+            }
+        }
+    }
+
+    #[test]
+    fn vehicle_integrators_give_different_trajectories() {
+        // a steady push from rest, well under max_speed so only the integration differs
+        let (force, steps) = (20.0, 60);
+        let dt = REFERENCE_TIME_STEP;
+        let end = |name: &str| {
+            let params = VehicleParams::builder().acceleration_damping(0.0).max_speed(1000.0).build();
+            let mut vehicle = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, params);
+            vehicle.integrator = integrator_from_name(name).expect("listed integrators have names");
+            for _ in 0..steps {
+                vehicle.apply_global_force(Vector2D::new(force, 0.0));
+                vehicle.update(dt);
+            }
+            vehicle.local_space.position.x
+        };
+
+        // explicit Euler lags a step behind, semi-implicit a step ahead of the exact a t^2 / 2
+        let n = steps as f32;
+        assert!((end("explicit-euler") - force * dt * dt * n * (n - 1.0) / 2.0).abs() < 1.0e-3);
+        assert!((end("semi-implicit-euler") - force * dt * dt * n * (n + 1.0) / 2.0).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn closest_approach_of_crossing_vehicles() {
+        let mut a = SimpleVehicle::new(Vector2D::new(-10.0, 0.0), MathMode::Exact);
+        a.velocity = Vector2D::new(5.0, 0.0);
+        let mut b = SimpleVehicle::new(Vector2D::new(0.0, -20.0), MathMode::Exact);
+        b.velocity = Vector2D::new(0.0, 5.0);
+
+        assert!(a.predict_future_position(2.0).approx_eq(Vector2D::new(0.0, 0.0)));
+
+        // relative position (10, -20), relative velocity (-5, 5), closest at t = 3
+        let approach = a.closest_approach(&b);
+        assert!((approach.time - 3.0).abs() < EPSILON);
+        assert!((approach.distance - 50.0_f32.sqrt()).abs() < 1.0e-4);
+        assert!(approach.position.approx_eq(Vector2D::new(5.0, 0.0)));
+        assert!(approach.other_position.approx_eq(Vector2D::new(0.0, -5.0)));
+        let reverse = b.closest_approach(&a);
+        assert_eq!((reverse.time, reverse.distance), (approach.time, approach.distance));
+    }
+
+    #[test]
+    fn closest_approach_when_moving_apart_is_now() {
+        let mut a = SimpleVehicle::new(Vector::new(0.0, 0.0, 0.0), MathMode::Exact);
+        a.velocity = Vector::new(0.0, 0.0, -1.0);
+        let mut b = SimpleVehicle::new(Vector::new(0.0, 0.0, 4.0), MathMode::Exact);
+        b.velocity = Vector::new(0.0, 0.0, 1.0);
+
+        let approach = a.closest_approach(&b);
+        assert_eq!(approach.time, 0.0);
+        assert_eq!(approach.distance, 4.0);
+    }
+}
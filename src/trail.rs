@@ -0,0 +1,146 @@
+// trail.rs
+// recent positions of a vehicle, sampled at a fixed interval into a ring buffer
+use std::collections::VecDeque;
+
+use ggez::glam::Vec2;
+use ggez::graphics::{self, Color, DrawParam};
+use ggez::{Context, GameResult};
+
+use crate::vector_space::VectorSpace;
+
+// six seconds of history at the default interval
+const DEFAULT_CAPACITY: usize = 120;
+const DEFAULT_SAMPLE_INTERVAL: f32 = 0.05;
+
+pub struct Trail<V: VectorSpace> {
+    // oldest first, the oldest sample is dropped once capacity is reached
+    positions: VecDeque<V>,
+    capacity: usize,
+    // seconds between samples
+    pub sample_interval: f32,
+    since_sample: f32,
+}
+
+impl<V: VectorSpace> Trail<V> {
+    pub fn new(capacity: usize, sample_interval: f32) -> Self {
+        assert!(capacity > 0, "trail capacity must be positive");
+        Trail {
+            positions: VecDeque::with_capacity(capacity),
+            capacity,
+            sample_interval,
+            since_sample: 0.0,
+        }
+    }
+
+    // called every step with the time since the last call, keeps a sample every sample_interval
+    pub fn record(&mut self, position: V, dt: f32) {
+        self.since_sample += dt;
+        if self.positions.is_empty() {
+            self.since_sample = 0.0;
+        } else if self.since_sample < self.sample_interval {
+            return;
+        } else {
+            // the leftover carries over so samples average sample_interval apart whatever dt is,
+            // a step longer than an interval still only records once
+            self.since_sample = (self.since_sample - self.sample_interval).min(self.sample_interval);
+        }
+        if self.positions.len() == self.capacity {
+            self.positions.pop_front();
+        }
+        self.positions.push_back(position);
+    }
+
+    pub fn clear(&mut self) {
+        self.positions.clear();
+        self.since_sample = 0.0;
+    }
+
+    // oldest first
+    pub fn positions(&self) -> impl Iterator<Item = &V> {
+        self.positions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<V: VectorSpace> Default for Trail<V> {
+    fn default() -> Self {
+        Trail::new(DEFAULT_CAPACITY, DEFAULT_SAMPLE_INTERVAL)
+    }
+}
+
+impl<V: VectorSpace + Into<Vec2>> Trail<V> {
+    // one line per sample up to current_position, fading out towards the oldest sample
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut graphics::Canvas, current_position: V, color: Color) -> GameResult {
+        if self.positions.is_empty() {
+            return Ok(());
+        }
+
+        let points: Vec<Vec2> = self.positions.iter().chain(std::iter::once(&current_position)).map(|&p| p.into()).collect();
+        let mut builder = graphics::MeshBuilder::new();
+        for (index, segment) in points.windows(2).enumerate() {
+            if segment[0] == segment[1] {
+                continue;
+            }
+            // age of the segment's older end in samples, the newest segment is fully opaque
+            let age = (self.positions.len() - 1 - index) as f32;
+            let alpha = color.a * (1.0 - age / self.capacity as f32);
+            builder.line(segment, 2.0, Color { a: alpha, ..color })?;
+        }
+
+        let mesh = graphics::Mesh::from_data(ctx, builder.build());
+        canvas.draw(&mesh, DrawParam::default());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector2d::Vector2D;
+
+    #[test]
+    fn samples_at_interval_and_drops_oldest() {
+        let mut trail = Trail::new(3, 0.5);
+        for step in 0..10 {
+            trail.record(Vector2D::new(step as f32, 0.0), 0.25);
+        }
+        // samples at steps 0, 2, 4, 6 and 8, only the last three are kept
+        let xs: Vec<f32> = trail.positions().map(|p| p.x).collect();
+        assert_eq!(xs, vec![4.0, 6.0, 8.0]);
+
+        trail.clear();
+        assert!(trail.is_empty());
+        trail.record(Vector2D::new(1.0, 1.0), 0.0);
+        assert_eq!(trail.len(), 1);
+    }
+
+    #[test]
+    fn sample_spacing_does_not_depend_on_dt() {
+        // 1/60 divides the interval, 0.02 does not and used to round it up to 0.06
+        for dt in [1.0 / 60.0, 0.02] {
+            let mut trail = Trail::new(1000, 0.05);
+            for step in 0..600 {
+                // x is the time of the sample
+                trail.record(Vector2D::new(step as f32 * dt, 0.0), dt);
+            }
+            let times: Vec<f32> = trail.positions().map(|p| p.x).collect();
+            for pair in times.windows(2) {
+                let gap = pair[1] - pair[0];
+                assert!(gap > 0.05 - dt - 1.0e-4 && gap < 0.05 + dt + 1.0e-4, "gap {} at dt {}", gap, dt);
+            }
+            let mean_gap = (times[times.len() - 1] - times[0]) / (times.len() - 1) as f32;
+            assert!((mean_gap - 0.05).abs() < 1.0e-3, "mean gap {} at dt {}", mean_gap, dt);
+        }
+    }
+}
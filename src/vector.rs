@@ -1,176 +1,437 @@
 // vector.rs
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use std::f32::consts::TAU;
+
 // random for generating random vectors
 use rand::Rng;
 
-#[derive(Debug, Copy, Clone)]
+use crate::random::gaussian;
+use crate::vector_space::VectorSpace;
+
+// tolerance used by approx_eq, loose enough to absorb f32 rounding in steering math
+pub const EPSILON: f32 = 1.0e-5;
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub struct Vector {
     pub x: f32,
     pub y: f32,
     pub z: f32, // added z for cross product
 }
 
+// length, normalized, truncated, lerp and the other generic vector math come from VectorSpace
 impl Vector {
-    pub fn new(x: f32, y: f32, z: f32) -> Self {
+    pub const ZERO: Vector = Vector { x: 0.0, y: 0.0, z: 0.0 };
+
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
         Vector { x, y, z }
     }
 
     pub fn set(&mut self, vec: Vector) {
-        self.x = vec.x;
-        self.y = vec.y;
-        self.z = vec.z;
+        *self = vec;
     }
 
     pub fn set_to_zero(&mut self) {
-        self.x = 0.0;
-        self.y = 0.0;
-        self.z = 0.0;
+        *self = Vector::ZERO;
+    }
+
+    pub fn dot(self, vec: Vector) -> f32 {
+        (self.x * vec.x) + (self.y * vec.y) + (self.z * vec.z)
     }
 
-    pub fn magnitude_squared(&self) -> f32{
-        return self.x.powi(2) + self.y.powi(2) + self.z.powi(2);
+    pub fn cross(self, vec: Vector) -> Vector {
+        Vector::new(
+            (self.y * vec.z) - (self.z * vec.y),
+            (self.z * vec.x) - (self.x * vec.z),
+            (self.x * vec.y) - (self.y * vec.x),
+        )
     }
 
-    pub fn magnitude(&self) -> f32 {
-        return (self.magnitude_squared()).sqrt();
+    // same as truncated but measured with approximate_length
+    pub fn approximate_truncated(self, max_length: f32) -> Vector {
+        let length = self.approximate_length();
+        if length > max_length {
+            self * (max_length / length)
+        } else {
+            self
+        }
+    }
+
+    // component-wise comparison within EPSILON
+    pub fn approx_eq(self, vec: Vector) -> bool {
+        self.approx_eq_eps(vec, EPSILON)
     }
 
-    // generates random unit vector values for a 3D model, 
-    // not sure if this is most efficient
-    pub fn gen_random_vector(&mut self){
-        let mut rng = rand::thread_rng();
-        self.x = rng.gen::<f32>() * 2.0 - 1.0;
-        self.y = rng.gen::<f32>() * 2.0 - 1.0;
-        self.z = rng.gen::<f32>() * 2.0 - 1.0;
+    pub fn approx_eq_eps(self, vec: Vector, epsilon: f32) -> bool {
+        (self.x - vec.x).abs() <= epsilon
+            && (self.y - vec.y).abs() <= epsilon
+            && (self.z - vec.z).abs() <= epsilon
+    }
 
-        while self.magnitude_squared() > 1.0 {
-            self.x = rng.gen::<f32>() * 2.0 - 1.0;
-            self.y = rng.gen::<f32>() * 2.0 - 1.0; 
-            self.z = rng.gen::<f32>() * 2.0 - 1.0;
+    pub fn is_approx_zero(self) -> bool {
+        self.approx_eq(Vector::ZERO)
+    }
+
+    // keeps the vector within max_angle radians of unit_basis, rotating it onto the cone
+    // surface when it points further away, length is preserved
+    pub fn limit_deviation_angle(self, max_angle: f32, unit_basis: Vector) -> Vector {
+        let length = self.length();
+        if length == 0.0 || self.dot(unit_basis) >= length * max_angle.cos() {
+            return self;
         }
+
+        let mut perpendicular = self.perpendicular_component(unit_basis).normalized();
+        if perpendicular.is_approx_zero() {
+            // straight behind the basis, any side is as good as another
+            perpendicular = unit_basis.any_perpendicular();
+        }
+        (unit_basis * max_angle.cos() + perpendicular * max_angle.sin()) * length
+    }
+
+    // rotates by angle radians about axis, counterclockwise looking down the axis (Rodrigues)
+    pub fn rotated_about_axis(self, axis: Vector, angle: f32) -> Vector {
+        let axis = axis.normalized();
+        let (sin, cos) = angle.sin_cos();
+        self * cos + axis.cross(self) * sin + axis * (axis.dot(self) * (1.0 - cos))
+    }
+
+    // quarter turn counterclockwise in the xy plane, z is dropped
+    pub fn perp_2d(self) -> Vector {
+        Vector::new(-self.y, self.x, 0.0)
+    }
+
+    // some unit vector perpendicular to this one
+    pub fn any_perpendicular(self) -> Vector {
+        let helper = if self.x.abs() < 0.9 { Vector::new(1.0, 0.0, 0.0) } else { Vector::new(0.0, 1.0, 0.0) };
+        self.cross(helper).normalized()
+    }
+
+    // distance from this point to the segment from start to end, and the nearest point on it
+    pub fn distance_to_segment(self, start: Vector, end: Vector) -> (f32, Vector) {
+        let segment = end - start;
+        let length_squared = segment.length_squared();
+        let nearest = if length_squared == 0.0 {
+            start
+        } else {
+            let t = ((self - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+            start + segment * t
+        };
+        (self.distance(nearest), nearest)
+    }
+
+    // generates a random vector inside the unit sphere for a 3D model,
+    // rejection sampling, takes the caller's rng so runs can be reproduced from a seed
+    pub fn gen_random_vector<R: Rng + ?Sized>(rng: &mut R) -> Vector {
+        loop {
+            let vec = Vector::new(
+                rng.gen::<f32>() * 2.0 - 1.0,
+                rng.gen::<f32>() * 2.0 - 1.0,
+                rng.gen::<f32>() * 2.0 - 1.0,
+            );
+            if vec.length_squared() <= 1.0 {
+                return vec;
+            }
+        }
+    }
+
+    // uniform inside the unit disk in the xy plane, for spawning in rings and jittered targets
+    pub fn random_in_disk<R: Rng + ?Sized>(rng: &mut R) -> Vector {
+        // sqrt keeps the density uniform over the area instead of bunching at the center
+        Vector::random_on_circle(rng) * rng.gen::<f32>().sqrt()
+    }
+
+    // uniform on the unit circle in the xy plane
+    pub fn random_on_circle<R: Rng + ?Sized>(rng: &mut R) -> Vector {
+        let angle = rng.gen::<f32>() * TAU;
+        Vector::new(angle.cos(), angle.sin(), 0.0)
+    }
+
+    // uniform on the surface of the unit sphere
+    pub fn random_unit_vector<R: Rng + ?Sized>(rng: &mut R) -> Vector {
+        let z = rng.gen::<f32>() * 2.0 - 1.0;
+        let angle = rng.gen::<f32>() * TAU;
+        let radius = (1.0 - z * z).sqrt();
+        Vector::new(radius * angle.cos(), radius * angle.sin(), z)
+    }
+
+    // unit vector uniformly distributed inside a cone of half_angle radians around axis.
+    // axis must not be zero, there is no cone around it and the result would be zero
+    pub fn random_in_cone<R: Rng + ?Sized>(rng: &mut R, axis: Vector, half_angle: f32) -> Vector {
+        debug_assert!(axis.length_squared() > 0.0, "random_in_cone needs a non-zero axis");
+        let axis = axis.normalized();
+        // cos of the angle from the axis is uniform over the spherical cap
+        let cos_theta = 1.0 - rng.gen::<f32>() * (1.0 - half_angle.cos());
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let angle = rng.gen::<f32>() * TAU;
+
+        // any two unit vectors perpendicular to axis and each other
+        let u = axis.any_perpendicular();
+        let w = axis.cross(u);
+
+        axis * cos_theta + (u * angle.cos() + w * angle.sin()) * sin_theta
+    }
+
+    // each component drawn from a normal distribution with mean 0
+    pub fn random_gaussian<R: Rng + ?Sized>(rng: &mut R, std_dev: f32) -> Vector {
+        Vector::new(gaussian(rng, std_dev), gaussian(rng, std_dev), gaussian(rng, std_dev))
+    }
+
+    // this vector plus gaussian noise, e.g. a jittered target
+    pub fn perturbed<R: Rng + ?Sized>(self, rng: &mut R, std_dev: f32) -> Vector {
+        self + Vector::random_gaussian(rng, std_dev)
     }
 
     // approximate length of Vector, this is a fast approximation method
-    pub fn approximate_length(&mut self) -> f32{
-        // get absolute value of x, y, and z 
-        let mut a = self.x.abs(); 
+    pub fn approximate_length(&self) -> f32 {
+        // get absolute value of x, y, and z
+        let mut a = self.x.abs();
         let mut b = self.y.abs();
         let mut c = self.z.abs();
 
-        // make sure a is the largest coordinate. 
+        // make sure a is the largest coordinate.
         if a < b {
-            let temp = a; 
-            a = b;
-            b = temp;
+            std::mem::swap(&mut a, &mut b);
         }
 
         if a < c {
-            let temp = a;
-            a = c;
-            c = temp;
+            std::mem::swap(&mut a, &mut c);
         }
 
-        return (a * 0.9375) + ((b + c) * 0.375);
+        (a * 0.9375) + ((b + c) * 0.375)
     }
 
-    // double check because old implementation has distTemp as static variable
-    pub fn approximate_distance(&mut self, vec: &mut Vector) -> f32 {
-        // disttemp should be difference between this vec
-        let mut dist_temp = Vector::new(0.0, 0.0, 0.0);
-        dist_temp.set_diff(*self, *vec);
-        return dist_temp.approximate_length();
+    pub fn approximate_distance(&self, vec: Vector) -> f32 {
+        (*self - vec).approximate_length()
     }
+}
 
-    pub fn set_sum(&mut self, vec: Vector, vec2: Vector) {
-        self.x = vec.x + vec2.x;
-        self.y = vec.y + vec2.y;
-        self.z = vec.z + vec2.z;
-    }
-    
-    pub fn set_diff(&mut self, vec: Vector, vec2: Vector){
-        self.x = vec.x - vec2.x;
-        self.y = vec.y - vec2.y;
-        self.z = vec.z - vec2.z;
+impl VectorSpace for Vector {
+    const ZERO: Vector = Vector::ZERO;
+    const FORWARD: Vector = Vector::new(0.0, 0.0, 1.0);
+    const SIDE: Vector = Vector::new(1.0, 0.0, 0.0);
+    const UP: Vector = Vector::new(0.0, 1.0, 0.0);
+
+    fn dot(self, vec: Vector) -> f32 {
+        Vector::dot(self, vec)
     }
 
-    pub fn set_scale(&mut self, scale_factor: f32, vec: Vector) {
-        self.x = vec.x * scale_factor;
-        self.y = vec.y * scale_factor;
-        self.z = vec.z * scale_factor;
+    fn approximate_length(self) -> f32 {
+        Vector::approximate_length(&self)
     }
 
-    pub fn set_cross(&mut self, vec: Vector, vec2: Vector){
-        self.x = (vec.y * vec2.z) - (vec.z * vec2.y);
-        self.y = (vec.z * vec2.x) - (vec.x * vec2.z);
-        self.z = (vec.x * vec2.y) - (vec.y * vec2.x);
+    fn gen_random_vector<R: Rng + ?Sized>(rng: &mut R) -> Vector {
+        Vector::gen_random_vector(rng)
     }
 
-    pub fn set_interpolation(&mut self, blend: f32, vec: &mut Vector, vec2: Vector){
-        // blending new acceleration with acceleration
-        self.x = vec.x + (blend * (vec2.x - vec.x));
-        self.y = vec.y + (blend * (vec2.y - vec.y));
-        self.z = vec.z + (blend * (vec2.z - vec.z));
+    fn random_unit_vector<R: Rng + ?Sized>(rng: &mut R) -> Vector {
+        Vector::random_unit_vector(rng)
     }
 
-    pub fn set_normalize(&mut self) {
-        let mag: f32 = self.magnitude();
-        if mag != 0.0 {
-            self.set_scale(1.0 / mag, *self);
+    // side = up x forward keeps the same handedness as LocalSpace::new
+    fn side_and_up(forward: Vector, up_hint: Vector) -> (Vector, Vector) {
+        let mut side = up_hint.cross(forward).normalized();
+        if side.is_approx_zero() {
+            // up_hint is parallel to forward, no way to tell which way to bank
+            side = forward.any_perpendicular();
         }
+        (side, forward.cross(side))
     }
 
-    // pub fn set_approximate_normalize(&mut self) {
-    //     let mag: f32 = self.approximate_length();
-    //     if mag != 0.0 {
-    //         self.set_scale(1.0 / mag, *self);
-    //     }
-    // }   
+    fn localize(vec: Vector, side: Vector, up: Vector, forward: Vector) -> Vector {
+        Vector::new(vec.dot(side), vec.dot(up), vec.dot(forward))
+    }
 
-    pub fn set_approximate_truncate(&mut self, threshold: f32) {
-        let length: f32 = self.approximate_length();
-        if length > threshold {
-            self.set_scale(threshold / length, *self);
-        }
+    fn globalize(local: Vector, side: Vector, up: Vector, forward: Vector) -> Vector {
+        side * local.x + up * local.y + forward * local.z
     }
+}
+
+impl Add for Vector {
+    type Output = Vector;
 
-    // pub fn scale_by(&mut self, scale_factor: f32) {
-    //     self.x *= scale_factor;
-    //     self.y *= scale_factor;
-    //     self.z *= scale_factor;
-    // }
-
-    // // fix this to have a return
-    // pub fn truncate(&mut self, max_val: f32) {
-    //     let mag = self.magnitude();
-    //     if mag > max_val {
-    //         let scale_factor = max_val / mag;
-    //         self.x *= scale_factor;
-    //         self.y *= scale_factor;
-    //         self.z *= scale_factor;
-    //     }
-    // }
+    fn add(self, rhs: Vector) -> Vector {
+        Vector::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
 }
 
-// I added these functions to try and overload but got rid of them during debugging, might still be issues
-// impl Add<Vector> for Vector {
-//     type Output = Vector;
-
-//     fn add(self, rhs: Vector) -> Vector {
-//         Vector {
-//             x: self.x + rhs.x,
-//             y: self.y + rhs.y,
-//             z: self.z + rhs.z,
-//         }
-//     }
-// }
-
-// impl Sub<Vector> for Vector {
-//     type Output = Vector;
-
-//     fn sub(self, rhs: Vector) -> Vector {
-//         Vector {
-//             x: self.x - rhs.x,
-//             y: self.y - rhs.y,
-//             z: self.z - rhs.z,
-//         }
-//     }
-// }
\ No newline at end of file
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<f32> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: f32) -> Vector {
+        Vector::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+// lets scalars go on the left, e.g. 0.5 * acceleration
+impl Mul<Vector> for f32 {
+    type Output = Vector;
+
+    fn mul(self, rhs: Vector) -> Vector {
+        rhs * self
+    }
+}
+
+impl Div<f32> for Vector {
+    type Output = Vector;
+
+    fn div(self, rhs: f32) -> Vector {
+        Vector::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<f32> for Vector {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign<f32> for Vector {
+    fn div_assign(&mut self, rhs: f32) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    use super::*;
+
+    #[test]
+    fn operators_match_component_math() {
+        let a = Vector::new(1.0, 2.0, 3.0);
+        let b = Vector::new(4.0, -5.0, 6.0);
+        assert_eq!(a + b, Vector::new(5.0, -3.0, 9.0));
+        assert_eq!(a - b, Vector::new(-3.0, 7.0, -3.0));
+        assert_eq!(a * 2.0, 2.0 * a);
+        assert_eq!(-a, Vector::new(-1.0, -2.0, -3.0));
+        assert_eq!(a.dot(b), 12.0);
+        assert_eq!(Vector::new(1.0, 0.0, 0.0).cross(Vector::new(0.0, 1.0, 0.0)), Vector::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn lerp_blends_between_endpoints() {
+        let a = Vector::new(0.0, 0.0, 0.0);
+        let b = Vector::new(10.0, -4.0, 2.0);
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert!(a.lerp(b, 0.25).approx_eq(Vector::new(2.5, -1.0, 0.5)));
+    }
+
+    #[test]
+    fn components_split_against_basis() {
+        let vec = Vector::new(3.0, 4.0, 5.0);
+        let basis = Vector::new(0.0, 1.0, 0.0);
+        assert!(vec.parallel_component(basis).approx_eq(Vector::new(0.0, 4.0, 0.0)));
+        assert!(vec.perpendicular_component(basis).approx_eq(Vector::new(3.0, 0.0, 5.0)));
+        assert!((vec.parallel_component(basis) + vec.perpendicular_component(basis)).approx_eq(vec));
+    }
+
+    #[test]
+    fn deviation_is_clamped_to_cone() {
+        let basis = Vector::new(1.0, 0.0, 0.0);
+
+        // inside the cone nothing changes
+        let inside = Vector::new(2.0, 0.5, 0.0);
+        assert_eq!(inside.limit_deviation_angle(FRAC_PI_4, basis), inside);
+
+        // straight sideways gets pulled onto the 45 degree edge, keeping its length
+        let sideways = Vector::new(0.0, 2.0, 0.0);
+        let limited = sideways.limit_deviation_angle(FRAC_PI_4, basis);
+        let edge = 2.0 * FRAC_PI_4.cos();
+        assert!(limited.approx_eq(Vector::new(edge, edge, 0.0)));
+
+        // straight backwards still ends up on the cone
+        let limited = Vector::new(-1.0, 0.0, 0.0).limit_deviation_angle(FRAC_PI_4, basis);
+        assert!((limited.length() - 1.0).abs() < EPSILON);
+        assert!((limited.dot(basis) - FRAC_PI_4.cos()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn rotation_about_axis() {
+        let x = Vector::new(1.0, 0.0, 0.0);
+        let z = Vector::new(0.0, 0.0, 1.0);
+        assert!(x.rotated_about_axis(z, FRAC_PI_2).approx_eq(Vector::new(0.0, 1.0, 0.0)));
+        assert!(x.rotated_about_axis(x, FRAC_PI_2).approx_eq(x));
+        // axis doesn't need to be unit length
+        assert!(z.rotated_about_axis(x * 3.0, FRAC_PI_2).approx_eq(Vector::new(0.0, -1.0, 0.0)));
+    }
+
+    #[test]
+    fn perp_2d_turns_counterclockwise() {
+        assert_eq!(Vector::new(1.0, 0.0, 5.0).perp_2d(), Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(Vector::new(3.0, 4.0, 0.0).perp_2d().dot(Vector::new(3.0, 4.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn distance_to_segment_finds_nearest_point() {
+        let start = Vector::new(0.0, 0.0, 0.0);
+        let end = Vector::new(10.0, 0.0, 0.0);
+
+        // above the middle of the segment
+        let (distance, nearest) = Vector::new(4.0, 3.0, 0.0).distance_to_segment(start, end);
+        assert!((distance - 3.0).abs() < EPSILON);
+        assert!(nearest.approx_eq(Vector::new(4.0, 0.0, 0.0)));
+
+        // past the end the endpoint is nearest
+        let (distance, nearest) = Vector::new(13.0, 4.0, 0.0).distance_to_segment(start, end);
+        assert!((distance - 5.0).abs() < EPSILON);
+        assert_eq!(nearest, end);
+
+        // degenerate segment is a point
+        let (distance, nearest) = Vector::new(0.0, 2.0, 0.0).distance_to_segment(start, start);
+        assert!((distance - 2.0).abs() < EPSILON);
+        assert_eq!(nearest, start);
+    }
+
+    #[test]
+    fn random_generators_stay_in_their_shapes() {
+        let mut rng = crate::random::seeded_rng(7);
+        let axis = Vector::new(1.0, 2.0, -2.0);
+        let half_angle = 0.3;
+        for _ in 0..1000 {
+            let in_disk = Vector::random_in_disk(&mut rng);
+            assert!(in_disk.length() <= 1.0 && in_disk.z == 0.0);
+            let on_circle = Vector::random_on_circle(&mut rng);
+            assert!((on_circle.length() - 1.0).abs() < EPSILON && on_circle.z == 0.0);
+            assert!(Vector::gen_random_vector(&mut rng).length() <= 1.0);
+            assert!((Vector::random_unit_vector(&mut rng).length() - 1.0).abs() < EPSILON);
+
+            let in_cone = Vector::random_in_cone(&mut rng, axis, half_angle);
+            assert!((in_cone.length() - 1.0).abs() < EPSILON);
+            assert!(in_cone.dot(axis.normalized()).clamp(-1.0, 1.0).acos() <= half_angle + 1.0e-3);
+        }
+
+        // the sphere samples should cover it, not bunch on one side
+        let mean = (0..4000).fold(Vector::ZERO, |total, _| total + Vector::random_unit_vector(&mut rng)) / 4000.0;
+        assert!(mean.length() < 0.05, "mean {:?}", mean);
+    }
+}
@@ -0,0 +1,238 @@
+// vector2d.rs
+// 2D counterpart of Vector for the demos, no z to drift or zero by hand
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use crate::random::gaussian;
+use crate::vector::EPSILON;
+use crate::vector_space::VectorSpace;
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Vector2D {
+    pub x: f32,
+    pub y: f32,
+}
+
+// the generic vector math comes from VectorSpace, as for Vector
+impl Vector2D {
+    pub const ZERO: Vector2D = Vector2D { x: 0.0, y: 0.0 };
+
+    pub const fn new(x: f32, y: f32) -> Self {
+        Vector2D { x, y }
+    }
+
+    pub fn dot(self, vec: Vector2D) -> f32 {
+        (self.x * vec.x) + (self.y * vec.y)
+    }
+
+    // z component of the 3D cross product, positive when vec is counterclockwise from self
+    pub fn cross(self, vec: Vector2D) -> f32 {
+        (self.x * vec.y) - (self.y * vec.x)
+    }
+
+    // quarter turn counterclockwise
+    pub fn perp(self) -> Vector2D {
+        Vector2D::new(-self.y, self.x)
+    }
+
+    // rotates counterclockwise by angle radians
+    pub fn rotated(self, angle: f32) -> Vector2D {
+        let (sin, cos) = angle.sin_cos();
+        Vector2D::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    // component-wise comparison within EPSILON
+    pub fn approx_eq(self, vec: Vector2D) -> bool {
+        self.approx_eq_eps(vec, EPSILON)
+    }
+
+    pub fn approx_eq_eps(self, vec: Vector2D, epsilon: f32) -> bool {
+        (self.x - vec.x).abs() <= epsilon && (self.y - vec.y).abs() <= epsilon
+    }
+
+    pub fn is_approx_zero(self) -> bool {
+        self.approx_eq(Vector2D::ZERO)
+    }
+
+    // random vector inside the unit disk, rejection sampling like Vector::gen_random_vector
+    pub fn gen_random_vector<R: Rng + ?Sized>(rng: &mut R) -> Vector2D {
+        loop {
+            let vec = Vector2D::new(rng.gen::<f32>() * 2.0 - 1.0, rng.gen::<f32>() * 2.0 - 1.0);
+            if vec.length_squared() <= 1.0 {
+                return vec;
+            }
+        }
+    }
+
+    // uniform inside the unit disk
+    pub fn random_in_disk<R: Rng + ?Sized>(rng: &mut R) -> Vector2D {
+        Vector2D::random_on_circle(rng) * rng.gen::<f32>().sqrt()
+    }
+
+    // uniform on the unit circle
+    pub fn random_on_circle<R: Rng + ?Sized>(rng: &mut R) -> Vector2D {
+        let angle = rng.gen::<f32>() * TAU;
+        Vector2D::new(angle.cos(), angle.sin())
+    }
+
+    // unit vector uniformly within half_angle radians of axis, the 2D cone is an arc.
+    // axis must not be zero, atan2 would quietly treat it as +x
+    pub fn random_in_cone<R: Rng + ?Sized>(rng: &mut R, axis: Vector2D, half_angle: f32) -> Vector2D {
+        debug_assert!(axis.length_squared() > 0.0, "random_in_cone needs a non-zero axis");
+        let offset = (rng.gen::<f32>() * 2.0 - 1.0) * half_angle;
+        let angle = axis.y.atan2(axis.x) + offset;
+        Vector2D::new(angle.cos(), angle.sin())
+    }
+
+    // each component drawn from a normal distribution with mean 0
+    pub fn random_gaussian<R: Rng + ?Sized>(rng: &mut R, std_dev: f32) -> Vector2D {
+        Vector2D::new(gaussian(rng, std_dev), gaussian(rng, std_dev))
+    }
+
+    // this vector plus gaussian noise
+    pub fn perturbed<R: Rng + ?Sized>(self, rng: &mut R, std_dev: f32) -> Vector2D {
+        self + Vector2D::random_gaussian(rng, std_dev)
+    }
+
+    // Vector::approximate_length with z = 0
+    pub fn approximate_length(&self) -> f32 {
+        let a = self.x.abs().max(self.y.abs());
+        let b = self.x.abs().min(self.y.abs());
+        (a * 0.9375) + (b * 0.375)
+    }
+
+    pub fn approximate_distance(&self, vec: Vector2D) -> f32 {
+        (*self - vec).approximate_length()
+    }
+}
+
+impl VectorSpace for Vector2D {
+    const ZERO: Vector2D = Vector2D::ZERO;
+    const FORWARD: Vector2D = Vector2D::new(0.0, 1.0);
+    const SIDE: Vector2D = Vector2D::new(1.0, 0.0);
+    const UP: Vector2D = Vector2D::ZERO;
+
+    fn dot(self, vec: Vector2D) -> f32 {
+        Vector2D::dot(self, vec)
+    }
+
+    fn approximate_length(self) -> f32 {
+        Vector2D::approximate_length(&self)
+    }
+
+    fn gen_random_vector<R: Rng + ?Sized>(rng: &mut R) -> Vector2D {
+        Vector2D::gen_random_vector(rng)
+    }
+
+    fn random_unit_vector<R: Rng + ?Sized>(rng: &mut R) -> Vector2D {
+        Vector2D::random_on_circle(rng)
+    }
+
+    // in the plane side is always forward turned a quarter clockwise, there is nothing to bank
+    fn side_and_up(forward: Vector2D, _up_hint: Vector2D) -> (Vector2D, Vector2D) {
+        (-forward.perp(), Vector2D::ZERO)
+    }
+
+    fn localize(vec: Vector2D, side: Vector2D, _up: Vector2D, forward: Vector2D) -> Vector2D {
+        Vector2D::new(vec.dot(side), vec.dot(forward))
+    }
+
+    fn globalize(local: Vector2D, side: Vector2D, _up: Vector2D, forward: Vector2D) -> Vector2D {
+        side * local.x + forward * local.y
+    }
+}
+
+impl Add for Vector2D {
+    type Output = Vector2D;
+
+    fn add(self, rhs: Vector2D) -> Vector2D {
+        Vector2D::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vector2D {
+    type Output = Vector2D;
+
+    fn sub(self, rhs: Vector2D) -> Vector2D {
+        Vector2D::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<f32> for Vector2D {
+    type Output = Vector2D;
+
+    fn mul(self, rhs: f32) -> Vector2D {
+        Vector2D::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Mul<Vector2D> for f32 {
+    type Output = Vector2D;
+
+    fn mul(self, rhs: Vector2D) -> Vector2D {
+        rhs * self
+    }
+}
+
+impl Div<f32> for Vector2D {
+    type Output = Vector2D;
+
+    fn div(self, rhs: f32) -> Vector2D {
+        Vector2D::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl Neg for Vector2D {
+    type Output = Vector2D;
+
+    fn neg(self) -> Vector2D {
+        Vector2D::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Vector2D {
+    fn add_assign(&mut self, rhs: Vector2D) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Vector2D {
+    fn sub_assign(&mut self, rhs: Vector2D) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<f32> for Vector2D {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign<f32> for Vector2D {
+    fn div_assign(&mut self, rhs: f32) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_generators_stay_in_their_shapes() {
+        let mut rng = crate::random::seeded_rng(7);
+        let axis = Vector2D::new(-3.0, 1.0);
+        let half_angle = 0.5;
+        for _ in 0..1000 {
+            assert!(Vector2D::random_in_disk(&mut rng).length() <= 1.0);
+            assert!((Vector2D::random_on_circle(&mut rng).length() - 1.0).abs() < EPSILON);
+
+            let in_cone = Vector2D::random_in_cone(&mut rng, axis, half_angle);
+            assert!((in_cone.length() - 1.0).abs() < EPSILON);
+            assert!(in_cone.dot(axis.normalized()).clamp(-1.0, 1.0).acos() <= half_angle + 1.0e-3);
+        }
+    }
+}
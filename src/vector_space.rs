@@ -0,0 +1,97 @@
+// vector_space.rs
+// what the steering code needs from a vector, implemented by the 3D Vector and the 2D Vector2D
+// so SimpleVehicle, LocalSpace and the behaviors can run in either dimension
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use rand::Rng;
+
+pub trait VectorSpace:
+    Copy
+    + Debug
+    + Default
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<f32, Output = Self>
+    + Div<f32, Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign<f32>
+    + DivAssign<f32>
+{
+    const ZERO: Self;
+    // axes of an unrotated LocalSpace, UP is zero in 2D since there is no in-plane up
+    const FORWARD: Self;
+    const SIDE: Self;
+    const UP: Self;
+
+    fn dot(self, vec: Self) -> f32;
+
+    // the 0.9375/0.375 fast length estimate
+    fn approximate_length(self) -> f32;
+
+    // random vector inside the unit sphere (3D) or unit disk (2D)
+    fn gen_random_vector<R: Rng + ?Sized>(rng: &mut R) -> Self;
+
+    // random unit vector, on the sphere surface (3D) or the unit circle (2D)
+    fn random_unit_vector<R: Rng + ?Sized>(rng: &mut R) -> Self;
+
+    // unit side and up axes for a frame looking along unit forward, banked towards up_hint
+    fn side_and_up(forward: Self, up_hint: Self) -> (Self, Self);
+
+    // components of vec along the given axes, packed as (side, up, forward) in 3D
+    // and (side, forward) in 2D
+    fn localize(vec: Self, side: Self, up: Self, forward: Self) -> Self;
+
+    // inverse of localize, builds a world vector from local components
+    fn globalize(local: Self, side: Self, up: Self, forward: Self) -> Self;
+
+    fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    fn length(self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    fn distance(self, vec: Self) -> f32 {
+        (self - vec).length()
+    }
+
+    // unit vector in the same direction, the zero vector stays zero
+    fn normalized(self) -> Self {
+        let length = self.length();
+        if length != 0.0 {
+            self / length
+        } else {
+            self
+        }
+    }
+
+    // linear blend, blend 0.0 gives self and 1.0 gives vec
+    fn lerp(self, vec: Self, blend: f32) -> Self {
+        self + (vec - self) * blend
+    }
+
+    // part of this vector along unit_basis
+    fn parallel_component(self, unit_basis: Self) -> Self {
+        unit_basis * self.dot(unit_basis)
+    }
+
+    // part of this vector perpendicular to unit_basis
+    fn perpendicular_component(self, unit_basis: Self) -> Self {
+        self - self.parallel_component(unit_basis)
+    }
+
+    // same direction but no longer than max_length
+    fn truncated(self, max_length: f32) -> Self {
+        let length = self.length();
+        if length > max_length {
+            self * (max_length / length)
+        } else {
+            self
+        }
+    }
+}
@@ -0,0 +1,244 @@
+// vehicle_params.rs
+// per-vehicle tuning, speeds are world units per second and forces world units per second squared
+use crate::simple_vehicle::REFERENCE_TIME_STEP;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct VehicleParams {
+    pub mass: f32,
+    pub max_speed: f32,
+    pub max_force: f32,
+    // share of the old acceleration kept per REFERENCE_TIME_STEP, 0.0 means no smoothing
+    pub acceleration_damping: f32,
+    // size used for drawing, obstacle sweeps and the catch checks of the pursuit and avoidance scenes
+    pub radius: f32,
+    // how fast the smoothed curvature, acceleration and position follow the raw values, per
+    // second. the gap left after t seconds is exp(-rate * t) of where it started
+    pub curvature_smoothing: f32,
+    pub acceleration_smoothing: f32,
+    pub position_smoothing: f32,
+    // speed held by update on top of the other forces, None leaves speed to the steering
+    pub cruise_speed: Option<f32>,
+    // limits how fast forward turns to follow the velocity, None snaps it every step
+    pub turning: Option<TurnLimits>,
+}
+
+// radians per second and radians per second squared
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TurnLimits {
+    pub max_turn_rate: f32,
+    pub max_angular_acceleration: f32,
+}
+
+// OpenSteer's blend rates for path annotation
+const CURVATURE_SMOOTHING: f32 = 4.0;
+const ACCELERATION_SMOOTHING: f32 = 9.0;
+const POSITION_SMOOTHING: f32 = 0.06;
+
+// converts the per-frame values of the original applets to per-second units
+const fn per_second_from_per_frame(max_speed: f32, max_force: f32) -> (f32, f32) {
+    (max_speed / REFERENCE_TIME_STEP, max_force / (REFERENCE_TIME_STEP * REFERENCE_TIME_STEP))
+}
+
+impl VehicleParams {
+    // what the seek/flee demo runs with, 0.64 and 0.48 per frame
+    pub const fn demo() -> Self {
+        let (max_speed, max_force) = per_second_from_per_frame(0.64, 0.48);
+        VehicleParams {
+            mass: 1.0,
+            max_speed,
+            max_force,
+            acceleration_damping: 0.99,
+            radius: 7.5,
+            curvature_smoothing: CURVATURE_SMOOTHING,
+            acceleration_smoothing: ACCELERATION_SMOOTHING,
+            position_smoothing: POSITION_SMOOTHING,
+            cruise_speed: None,
+            turning: None,
+        }
+    }
+
+    // the original OpenSteer seek/flee values, 0.08 and 0.06 per frame
+    pub const fn opensteer() -> Self {
+        let (max_speed, max_force) = per_second_from_per_frame(0.08, 0.06);
+        VehicleParams {
+            mass: 1.0,
+            max_speed,
+            max_force,
+            acceleration_damping: 0.99,
+            // the demos are in pixels, keep the vehicle visible
+            radius: 7.5,
+            curvature_smoothing: CURVATURE_SMOOTHING,
+            acceleration_smoothing: ACCELERATION_SMOOTHING,
+            position_smoothing: POSITION_SMOOTHING,
+            cruise_speed: None,
+            turning: None,
+        }
+    }
+
+    // "demo" or "opensteer", as given to --preset
+    pub fn from_preset(name: &str) -> Option<Self> {
+        match name {
+            "demo" => Some(VehicleParams::demo()),
+            "opensteer" => Some(VehicleParams::opensteer()),
+            _ => None,
+        }
+    }
+
+    // starts from the demo preset
+    pub fn builder() -> VehicleParamsBuilder {
+        VehicleParamsBuilder { params: VehicleParams::demo() }
+    }
+
+    // starts from these values, handy for overriding one field of a preset
+    pub fn to_builder(self) -> VehicleParamsBuilder {
+        VehicleParamsBuilder { params: self }
+    }
+}
+
+impl Default for VehicleParams {
+    fn default() -> Self {
+        VehicleParams::demo()
+    }
+}
+
+pub struct VehicleParamsBuilder {
+    params: VehicleParams,
+}
+
+impl VehicleParamsBuilder {
+    pub fn mass(mut self, mass: f32) -> Self {
+        self.params.mass = mass;
+        self
+    }
+
+    pub fn max_speed(mut self, max_speed: f32) -> Self {
+        self.params.max_speed = max_speed;
+        self
+    }
+
+    pub fn max_force(mut self, max_force: f32) -> Self {
+        self.params.max_force = max_force;
+        self
+    }
+
+    pub fn acceleration_damping(mut self, acceleration_damping: f32) -> Self {
+        self.params.acceleration_damping = acceleration_damping;
+        self
+    }
+
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.params.radius = radius;
+        self
+    }
+
+    pub fn curvature_smoothing(mut self, curvature_smoothing: f32) -> Self {
+        self.params.curvature_smoothing = curvature_smoothing;
+        self
+    }
+
+    pub fn acceleration_smoothing(mut self, acceleration_smoothing: f32) -> Self {
+        self.params.acceleration_smoothing = acceleration_smoothing;
+        self
+    }
+
+    pub fn position_smoothing(mut self, position_smoothing: f32) -> Self {
+        self.params.position_smoothing = position_smoothing;
+        self
+    }
+
+    pub fn cruise_speed(mut self, cruise_speed: Option<f32>) -> Self {
+        self.params.cruise_speed = cruise_speed;
+        self
+    }
+
+    pub fn turning(mut self, turning: Option<TurnLimits>) -> Self {
+        self.params.turning = turning;
+        self
+    }
+
+    // panics on values the update step can't work with, see try_build
+    pub fn build(self) -> VehicleParams {
+        self.try_build().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    // the params, or why the update step can't work with them
+    pub fn try_build(self) -> Result<VehicleParams, String> {
+        let params = self.params;
+        if params.mass <= 0.0 || params.mass.is_nan() {
+            return Err(format!("vehicle mass must be positive, got {}", params.mass));
+        }
+        if params.max_speed < 0.0 || params.max_speed.is_nan() {
+            return Err(format!("max_speed must not be negative, got {}", params.max_speed));
+        }
+        if params.max_force < 0.0 || params.max_force.is_nan() {
+            return Err(format!("max_force must not be negative, got {}", params.max_force));
+        }
+        if !(0.0..=1.0).contains(&params.acceleration_damping) {
+            return Err(format!("acceleration_damping must be between 0 and 1, got {}", params.acceleration_damping));
+        }
+        if params.radius < 0.0 || params.radius.is_nan() {
+            return Err(format!("radius must not be negative, got {}", params.radius));
+        }
+        let smoothing = [params.curvature_smoothing, params.acceleration_smoothing, params.position_smoothing];
+        if smoothing.iter().any(|rate| *rate < 0.0 || rate.is_nan()) {
+            return Err(String::from("smoothing rates must not be negative"));
+        }
+        if let Some(cruise_speed) = params.cruise_speed {
+            if !(0.0..=params.max_speed).contains(&cruise_speed) {
+                return Err(format!("cruise_speed must be between 0 and max_speed, got {}", cruise_speed));
+            }
+        }
+        if let Some(turning) = params.turning {
+            if !(turning.max_turn_rate > 0.0 && turning.max_angular_acceleration > 0.0) {
+                return Err(format!("turn limits must be positive, got {:?}", turning));
+            }
+        }
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_mode::MathMode;
+    use crate::simple_vehicle::SimpleVehicle;
+    use crate::vector2d::Vector2D;
+
+    #[test]
+    fn opensteer_preset_reproduces_the_per_frame_values() {
+        let params = VehicleParams::from_preset("opensteer").expect("opensteer is a preset");
+        assert!((params.max_speed * REFERENCE_TIME_STEP - 0.08).abs() < 1.0e-6);
+        assert!((params.max_force * REFERENCE_TIME_STEP * REFERENCE_TIME_STEP - 0.06).abs() < 1.0e-6);
+
+        let demo = VehicleParams::from_preset("demo").expect("demo is a preset");
+        assert_eq!(demo, VehicleParams::default());
+        assert!((demo.max_speed * REFERENCE_TIME_STEP - 0.64).abs() < 1.0e-6);
+        assert_eq!(VehicleParams::from_preset("fast"), None);
+    }
+
+    #[test]
+    fn builder_sets_fields_and_rejects_bad_values() {
+        let params = VehicleParams::builder().mass(2.0).max_speed(50.0).radius(3.0).cruise_speed(Some(20.0)).build();
+        assert_eq!((params.mass, params.max_speed, params.radius, params.cruise_speed), (2.0, 50.0, 3.0, Some(20.0)));
+        assert_eq!(params.max_force, VehicleParams::demo().max_force);
+
+        assert!(VehicleParams::builder().mass(0.0).try_build().is_err());
+        assert!(VehicleParams::builder().max_force(-1.0).try_build().is_err());
+        assert!(VehicleParams::builder().acceleration_damping(1.5).try_build().is_err());
+        assert!(VehicleParams::builder().max_speed(10.0).cruise_speed(Some(20.0)).try_build().is_err());
+        let turning = TurnLimits { max_turn_rate: 0.0, max_angular_acceleration: 1.0 };
+        assert!(VehicleParams::builder().turning(Some(turning)).try_build().is_err());
+    }
+
+    #[test]
+    fn overrides_only_touch_one_vehicle() {
+        let preset = VehicleParams::opensteer();
+        let slow = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, preset);
+        let mut heavy = SimpleVehicle::with_params(Vector2D::ZERO, MathMode::Exact, preset.to_builder().mass(4.0).build());
+        heavy.params.max_force *= 2.0;
+
+        assert_eq!(slow.params, preset);
+        assert_eq!((heavy.params.mass, heavy.params.max_force), (4.0, preset.max_force * 2.0));
+        assert_eq!(heavy.params.max_speed, preset.max_speed);
+    }
+}